@@ -1,2 +1,5 @@
+pub mod connect;
+pub mod lobby;
 pub mod message;
+pub mod probe;
 pub mod state;
@@ -0,0 +1,41 @@
+use gg2_common::networking::{DEFAULT_PORT, error::NetworkError};
+use tokio::net::{TcpStream, lookup_host};
+
+/// Adds the default port to addresses without one
+pub fn with_default_port(address: &str) -> String {
+    if address.contains(':') {
+        address.to_string()
+    } else {
+        format!("{address}:{DEFAULT_PORT}")
+    }
+}
+
+/// Resolves an address and connects to the first server that answers
+pub async fn open_stream(address: &str) -> Result<TcpStream, NetworkError> {
+    let addresses = lookup_host(address)
+        .await
+        .map_err(|error| NetworkError::Resolve(error, address.to_string()))?
+        .collect::<Vec<_>>();
+
+    if addresses.is_empty() {
+        return Err(NetworkError::NoAddress(address.to_string()));
+    }
+
+    TcpStream::connect(&*addresses)
+        .await
+        .map_err(|error| NetworkError::Connection(error, address.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_port() {
+        assert_eq!(
+            with_default_port("localhost"),
+            format!("localhost:{DEFAULT_PORT}")
+        );
+        assert_eq!(with_default_port("localhost:1234"), "localhost:1234");
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    time::Duration,
+};
+
+use gg2_common::networking::{PROTOCOL_UUID, error::NetworkError};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use uuid::{Uuid, uuid};
+
+use super::connect::open_stream;
+
+/// The public lobby servers register with
+pub const DEFAULT_LOBBY_ADDRESS: &str = "ganggarrison.com:29944";
+/// Asks the lobby for its list rather than registering a server
+pub const LOBBY_LIST_UUID: Uuid = uuid!("297d0df4-430c-bf61-640a-640897eaef57");
+
+/// The lobby's transport for servers joined over TCP
+const TRANSPORT_TCP: u8 = 0;
+/// Set in a server's flags when joining needs a password
+const FLAG_PASSWORDED: u16 = 1;
+/// More servers than the lobby has ever listed
+const MAX_LOBBY_SERVERS: u32 = 4096;
+/// Well past the fixed fields and a generous set of info pairs
+const MAX_LOBBY_ENTRY_LENGTH: u32 = 16 * 1024;
+
+/// A server listed by the lobby
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LobbyServer {
+    pub address: SocketAddr,
+    pub name: String,
+    pub map: Option<String>,
+    pub players: u16,
+    pub bots: u16,
+    pub slots: u16,
+    pub passworded: bool,
+    /// Everything else the server registered, such as its version
+    pub info: BTreeMap<String, String>,
+}
+
+impl LobbyServer {
+    /// Decodes one entry of the lobby's list
+    ///
+    /// Servers that can't be joined over TCP are `None`
+    pub fn decode(entry: &[u8]) -> Result<Option<Self>, NetworkError> {
+        let mut reader = LobbyReader(entry);
+
+        let transport = reader.read_u8()?;
+        let port = reader.read_u16()?;
+        let ipv4 = Ipv4Addr::from(reader.read_array::<4>()?);
+        let ipv6 = Ipv6Addr::from(reader.read_array::<16>()?);
+        let slots = reader.read_u16()?;
+        let players = reader.read_u16()?;
+        let bots = reader.read_u16()?;
+        let flags = reader.read_u16()?;
+
+        let info_length = reader.read_u16()?;
+        let mut info = (0..info_length)
+            .map(|_| {
+                let key_length = reader.read_u8()?.into();
+                let key = reader.read_string(key_length)?;
+                let value_length = reader.read_u16()?.into();
+                let value = reader.read_string(value_length)?;
+
+                Ok((key, value))
+            })
+            .collect::<Result<BTreeMap<_, _>, NetworkError>>()?;
+
+        if transport != TRANSPORT_TCP {
+            return Ok(None);
+        }
+
+        // Servers without an IPv4 address only registered IPv6
+        let ip = if ipv4.is_unspecified() {
+            IpAddr::V6(ipv6)
+        } else {
+            IpAddr::V4(ipv4)
+        };
+        let address = SocketAddr::new(ip, port);
+
+        Ok(Some(Self {
+            address,
+            name: info.remove("name").unwrap_or_else(|| address.to_string()),
+            map: info.remove("map"),
+            players,
+            bots,
+            slots,
+            passworded: flags & FLAG_PASSWORDED != 0,
+            info,
+        }))
+    }
+}
+
+/// Reads the lobby's big-endian fields
+struct LobbyReader<'a>(&'a [u8]);
+
+impl LobbyReader<'_> {
+    fn read_bytes(&mut self, length: usize) -> Result<&[u8], NetworkError> {
+        let Some((bytes, rest)) = self.0.split_at_checked(length) else {
+            return Err(NetworkError::UnexpectedEOF);
+        };
+
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], NetworkError> {
+        self.read_bytes(N)?
+            .try_into()
+            .map_err(|_| NetworkError::UnexpectedEOF)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, NetworkError> {
+        self.read_array::<1>().map(u8::from_be_bytes)
+    }
+
+    fn read_u16(&mut self) -> Result<u16, NetworkError> {
+        self.read_array::<2>().map(u16::from_be_bytes)
+    }
+
+    fn read_string(&mut self, length: usize) -> Result<String, NetworkError> {
+        String::from_utf8(self.read_bytes(length)?.to_vec())
+            .map_err(|_| NetworkError::PacketPayload)
+    }
+}
+
+/// Lists the servers registered with a lobby that speak the client's protocol
+pub async fn fetch_servers(
+    lobby_address: &str,
+    timeout: Duration,
+) -> Result<Vec<LobbyServer>, NetworkError> {
+    tokio::time::timeout(timeout, request_list(lobby_address))
+        .await
+        .unwrap_or_else(|_| Err(NetworkError::ConnectTimeout(lobby_address.to_string())))
+}
+
+async fn request_list(lobby_address: &str) -> Result<Vec<LobbyServer>, NetworkError> {
+    let read_error = |error| NetworkError::Connection(error, lobby_address.to_string());
+
+    let mut stream = open_stream(lobby_address).await?;
+
+    let mut request = LOBBY_LIST_UUID.into_bytes().to_vec();
+    request.extend(PROTOCOL_UUID.into_bytes());
+    stream
+        .write_all(&request)
+        .await
+        .map_err(|_| NetworkError::PacketSend)?;
+
+    let server_length = stream.read_u32().await.map_err(read_error)?;
+    if server_length > MAX_LOBBY_SERVERS {
+        return Err(NetworkError::PacketPayload);
+    }
+
+    let mut servers = Vec::new();
+
+    for _ in 0..server_length {
+        let entry_length = stream.read_u32().await.map_err(read_error)?;
+        if entry_length > MAX_LOBBY_ENTRY_LENGTH {
+            return Err(NetworkError::PacketPayload);
+        }
+
+        let mut entry = vec![0; entry_length as usize];
+        stream.read_exact(&mut entry).await.map_err(read_error)?;
+
+        servers.extend(LobbyServer::decode(&entry)?);
+    }
+
+    Ok(servers)
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    fn encode(server: &LobbyServer, transport: u8) -> Vec<u8> {
+        let mut entry = vec![transport];
+        entry.extend(server.address.port().to_be_bytes());
+
+        match server.address.ip() {
+            IpAddr::V4(ip) => {
+                entry.extend(ip.octets());
+                entry.extend(Ipv6Addr::UNSPECIFIED.octets());
+            }
+            IpAddr::V6(ip) => {
+                entry.extend(Ipv4Addr::UNSPECIFIED.octets());
+                entry.extend(ip.octets());
+            }
+        }
+
+        entry.extend(server.slots.to_be_bytes());
+        entry.extend(server.players.to_be_bytes());
+        entry.extend(server.bots.to_be_bytes());
+        entry.extend(u16::from(server.passworded).to_be_bytes());
+
+        let mut info = server.info.clone();
+        info.insert("name".to_string(), server.name.clone());
+        if let Some(map) = &server.map {
+            info.insert("map".to_string(), map.clone());
+        }
+
+        entry.extend((info.len() as u16).to_be_bytes());
+        for (key, value) in info {
+            entry.push(key.len() as u8);
+            entry.extend(key.as_bytes());
+            entry.extend((value.len() as u16).to_be_bytes());
+            entry.extend(value.as_bytes());
+        }
+
+        entry
+    }
+
+    fn server(name: &str, address: SocketAddr) -> LobbyServer {
+        LobbyServer {
+            address,
+            name: name.to_string(),
+            map: Some("ctf_truefort".to_string()),
+            players: 7,
+            bots: 0,
+            slots: 24,
+            passworded: false,
+            info: BTreeMap::from([("game_ver".to_string(), "2.9.2".to_string())]),
+        }
+    }
+
+    #[test]
+    fn decode_entry() {
+        let mut listed = server("Ferris' Server", "10.0.0.1:8190".parse().unwrap());
+        listed.passworded = true;
+
+        assert_eq!(
+            LobbyServer::decode(&encode(&listed, TRANSPORT_TCP)).unwrap(),
+            Some(listed.clone())
+        );
+        assert_eq!(LobbyServer::decode(&encode(&listed, 1)).unwrap(), None);
+        assert!(matches!(
+            LobbyServer::decode(&encode(&listed, TRANSPORT_TCP)[..20]),
+            Err(NetworkError::UnexpectedEOF)
+        ));
+    }
+
+    /// Stands in for the lobby, answering one list request with `response`
+    async fn canned_lobby(response: Vec<u8>) -> String {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let lobby_address = listener.local_addr().unwrap().to_string();
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let mut request = [0; 32];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!(request[..16], LOBBY_LIST_UUID.into_bytes());
+            assert_eq!(request[16..], PROTOCOL_UUID.into_bytes());
+
+            stream.write_all(&response).await.unwrap();
+        });
+
+        lobby_address
+    }
+
+    #[tokio::test]
+    async fn fetch_listed_servers() {
+        let listed = vec![
+            server("First", "10.0.0.1:8190".parse().unwrap()),
+            server("Second", "[2001:db8::1]:8191".parse().unwrap()),
+        ];
+
+        let mut response = (listed.len() as u32 + 1).to_be_bytes().to_vec();
+        for server in &listed {
+            let entry = encode(server, TRANSPORT_TCP);
+            response.extend((entry.len() as u32).to_be_bytes());
+            response.extend(entry);
+        }
+
+        // Not joinable, so left out
+        let entry = encode(&listed[0], 1);
+        response.extend((entry.len() as u32).to_be_bytes());
+        response.extend(entry);
+
+        let lobby_address = canned_lobby(response).await;
+        let servers = fetch_servers(&lobby_address, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        assert_eq!(servers, listed);
+    }
+
+    #[tokio::test]
+    async fn reject_oversized_lists() {
+        let mut oversized_entry = 1u32.to_be_bytes().to_vec();
+        oversized_entry.extend(u32::MAX.to_be_bytes());
+
+        for response in [u32::MAX.to_be_bytes().to_vec(), oversized_entry] {
+            let lobby_address = canned_lobby(response).await;
+
+            assert!(matches!(
+                fetch_servers(&lobby_address, Duration::from_secs(5)).await,
+                Err(NetworkError::PacketPayload)
+            ));
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use gg2_common::{
     error::Result,
-    networking::{PacketKind, message::MessageWriter},
+    networking::{
+        PacketKind,
+        message::{MessageReader, MessageWriter},
+    },
     string::GGStringShort,
 };
 
@@ -12,24 +15,23 @@ pub trait ClientNetworkSerialize: Sized {
 }
 
 pub trait ClientNetworkDeserialize: Sized {
-    fn deserialize<I, C>(payload: &mut I, context: &C) -> impl Future<Output = Result<Self>>
+    fn deserialize<C>(payload: &mut MessageReader, context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext;
 }
 
 pub trait ClientNetworkDeserializationContext {
-    fn players_length(&self) -> impl Future<Output = u8>;
+    fn players_length(&self) -> u8;
 
-    fn deserialize_gamemode_state<I>(
-        &self,
-        payload: &mut I,
+    fn deserialize_gamemode_state(
+        &mut self,
+        payload: &mut MessageReader,
         kind: PacketKind,
-    ) -> impl Future<Output = Result<()>>
-    where
-        I: Iterator<Item = u8>;
+    ) -> Result<()>;
+
+    fn current_map_control_points_length(&self) -> Result<u8>;
 
-    fn current_map_control_points_length(&self) -> impl Future<Output = Result<u8>>;
+    fn current_map_moving_platforms_length(&self) -> Result<u8>;
 }
 
 impl ClientNetworkSerialize for &GGStringShort {
@@ -1,4 +1,8 @@
-use gg2_common::{error::Result, networking::message::*, player::RawInput};
+use gg2_common::{
+    error::Result,
+    networking::{error::NetworkError, message::*},
+    player::RawInput,
+};
 
 use super::ClientNetworkSerialize;
 
@@ -17,14 +21,34 @@ macro_rules! generic_message {
 }
 
 generic_message!(ClientMessageGeneric {
+    ClientSettings,
+    DownloadMap,
     Hello,
     InputState,
+    Ping,
     PlayerChangeClass,
     PlayerChangeTeam,
     PlayerJoin,
+    PluginPacket,
     ReserveSlot,
+    RewardChallengeResponse,
+    RewardRequest,
 });
 
+impl ClientNetworkSerialize for ClientClientSettings {
+    async fn serialize(self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.write_bool(self.queue_jump);
+
+        Ok(())
+    }
+}
+
+impl ClientNetworkSerialize for ClientDownloadMap {
+    async fn serialize(self, _buffer: &mut Vec<u8>) -> Result<()> {
+        Ok(())
+    }
+}
+
 impl ClientNetworkSerialize for ClientHello {
     async fn serialize(self, buffer: &mut Vec<u8>) -> Result<()> {
         let protocol_bytes = self.protocol.into_bytes();
@@ -51,6 +75,20 @@ impl ClientNetworkSerialize for ClientInputState {
     }
 }
 
+impl ClientNetworkSerialize for ClientPassword {
+    async fn serialize(self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.write_utf8_short_string(&self.password);
+
+        Ok(())
+    }
+}
+
+impl ClientNetworkSerialize for ClientPing {
+    async fn serialize(self, _buffer: &mut Vec<u8>) -> Result<()> {
+        Ok(())
+    }
+}
+
 impl ClientNetworkSerialize for ClientPlayerChangeClass {
     async fn serialize(self, buffer: &mut Vec<u8>) -> Result<()> {
         buffer.write_u8(self.class as u8);
@@ -73,6 +111,19 @@ impl ClientNetworkSerialize for ClientPlayerJoin {
     }
 }
 
+impl ClientNetworkSerialize for ClientPluginPacket {
+    async fn serialize(self, buffer: &mut Vec<u8>) -> Result<()> {
+        // The length includes the plugin id
+        let length = u16::try_from(self.payload.len() + 1).map_err(NetworkError::ListLength)?;
+
+        buffer.write_u16(length);
+        buffer.write_u8(self.plugin_id);
+        buffer.extend(self.payload);
+
+        Ok(())
+    }
+}
+
 impl ClientNetworkSerialize for ClientReserveSlot {
     async fn serialize(self, buffer: &mut Vec<u8>) -> Result<()> {
         buffer.write_utf8_short_string(&self.player_name);
@@ -80,3 +131,19 @@ impl ClientNetworkSerialize for ClientReserveSlot {
         Ok(())
     }
 }
+
+impl ClientNetworkSerialize for ClientRewardChallengeResponse {
+    async fn serialize(self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.extend(self.response);
+
+        Ok(())
+    }
+}
+
+impl ClientNetworkSerialize for ClientRewardRequest {
+    async fn serialize(self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.write_utf8_short_string(&self.reward_name);
+
+        Ok(())
+    }
+}
@@ -2,10 +2,16 @@ use std::time::Duration;
 
 use gg2_common::{
     error::{CommonError, Result},
-    game::{control_point::RawControlPoint, generator::RawGenerator, intel::RawIntel},
+    game::{
+        control_point::RawControlPoint, generator::RawGenerator, intel::RawIntel,
+        moving_platform::RawMovingPlatform,
+    },
     hud::{GamemodeHudArenaFull, HudKothTimer, HudMatchTimer},
-    networking::{PacketKind, error::NetworkError as Error, message::*},
-    player::{PlayerId, RawAdditionalPlayerInfo, RawInput, RawPlayerInfo, team::Captures},
+    networking::{PacketKind, error::NetworkError as Error, message::*, plugin::parse_plugin_list},
+    player::{
+        PlayerId, RawAdditionalPlayerInfo, RawInput, RawPlayerInfo,
+        team::{Captures, TeamSpawnable},
+    },
 };
 
 use super::{ClientNetworkDeserializationContext, ClientNetworkDeserialize};
@@ -13,14 +19,14 @@ use super::{ClientNetworkDeserializationContext, ClientNetworkDeserialize};
 macro_rules! generic_message {
     ($name: ident {$($case: ident),+$(,)?}) => {
         impl ClientNetworkDeserialize for $name {
-            async fn deserialize<I, C>(payload: &mut I, context: &C) -> Result<Self> where I: Iterator<Item = u8>, C: ClientNetworkDeserializationContext {
+            fn deserialize<C>(payload: &mut MessageReader, context: &mut C) -> Result<Self> where C: ClientNetworkDeserializationContext {
                 let raw_kind = payload.read_u8()?;
                 let kind = raw_kind
                     .try_into()
                     .map_err(|_| Error::PacketKind(raw_kind))?;
 
                 match kind {
-                    $(PacketKind::$case => Ok(ServerMessageGeneric::$case(${concat(Server, $case)}::deserialize(payload, context).await?))),+,
+                    $(PacketKind::$case => Ok(ServerMessageGeneric::$case(${concat(Server, $case)}::deserialize(payload, context)?))),+,
                     _ => Err(CommonError::Network(Error::IncorrectMessage(kind))),
                 }
             }
@@ -41,57 +47,56 @@ generic_message!(ServerMessageGeneric {
     QuickUpdate,
     PlayerDeath,
     ServerFull,
-    //RedTeamCap = 12,
-    //BlueTeamCap = 13,
-    //MapEnd = 14,
+    RedTeamCap,
+    BlueTeamCap,
+    MapEnd,
     ChatBubble,
-    //BuildSentry = 16,
-    //DestroySentry = 17,
-    //Balance = 18,
+    BuildSentry,
+    DestroySentry,
+    Balance,
     GrabIntel,
     ScoreIntel,
     DropIntel,
-    //UberCharged = 22,
-    //Uber = 23,
+    UberCharged,
+    Uber,
     Omnom,
     PasswordRequest,
     PasswordWrong,
     CaptureUpdate,
-    //CpCaptured = 30,
+    CpCaptured,
     PlayerChangeName,
-    //GeneratorDestroy = 32,
-    //ArenaWaitForPlayers = 33,
-    //ArenaEndround = 34,
-    //ArenaRestart = 35,
-    //UnlockCp = 36,
-    //ServerKick = 37,
-    //Kick = 38,
-    //KickName = 39,
-    //ArenaStartround = 40,
-    //ToggleZoom = 41,
+    GeneratorDestroy,
+    ArenaWaitForPlayers,
+    ArenaEndround,
+    ArenaRestart,
+    UnlockCp,
+    ServerKick,
+    Kick,
+    KickName,
+    ArenaStartround,
+    ToggleZoom,
     ReturnIntel,
     IncompatibleProtocol,
     JoinUpdate,
-    //DownloadMap = 45,
-    //SentryPosition = 46,
-    //RewardUpdate = 47,
+    DownloadMap,
+    SentryPosition,
+    RewardUpdate,
     //RewardRequest = 50,
-    //RewardChallengeCode = 51,
+    RewardChallengeCode,
     //RewardChallengeResponse = 52,
     MessageString,
     WeaponFire,
-    //PluginPacket = 55,
-    //KickBadPluginPacket = 56,
-    //Ping = 57,
-    //ClientSettings = 58,
-    //KickMultiClient = 59,
+    PluginPacket,
+    KickBadPluginPacket,
+    Ping,
+    // ClientSettings = 58 is only sent by clients
+    KickMultiClient,
     ReserveSlot,
 });
 
 impl ClientNetworkDeserialize for Captures {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let red_captures = payload.read_u8()?;
@@ -106,12 +111,13 @@ impl ClientNetworkDeserialize for Captures {
     }
 }
 
-pub trait ClientNetworkDeserializeHudMatchTimer: Sized {
-    fn deserialize(payload: &mut dyn Iterator<Item = u8>) -> Result<Self>;
+/// Gamemode HUD state is read without a context by the gamemode itself
+pub trait ClientNetworkDeserializeHud: Sized {
+    fn deserialize(payload: &mut MessageReader) -> Result<Self>;
 }
 
-impl ClientNetworkDeserializeHudMatchTimer for HudMatchTimer {
-    fn deserialize(mut payload: &mut dyn Iterator<Item = u8>) -> Result<Self> {
+impl ClientNetworkDeserializeHud for HudMatchTimer {
+    fn deserialize(payload: &mut MessageReader) -> Result<Self> {
         let total = payload.read_u8().map(u64::from).map(Duration::from_mins)?;
         let left = payload
             .read_u32()
@@ -122,12 +128,8 @@ impl ClientNetworkDeserializeHudMatchTimer for HudMatchTimer {
     }
 }
 
-impl ClientNetworkDeserialize for RawGenerator {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
-    where
-        I: Iterator<Item = u8>,
-        C: ClientNetworkDeserializationContext,
-    {
+impl ClientNetworkDeserializeHud for RawGenerator {
+    fn deserialize(payload: &mut MessageReader) -> Result<Self> {
         let health = payload.read_u16()?;
         let shield_health = payload.read_u16()?;
 
@@ -138,12 +140,8 @@ impl ClientNetworkDeserialize for RawGenerator {
     }
 }
 
-impl ClientNetworkDeserialize for GamemodeHudArenaFull {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
-    where
-        I: Iterator<Item = u8>,
-        C: ClientNetworkDeserializationContext,
-    {
+impl ClientNetworkDeserializeHud for GamemodeHudArenaFull {
+    fn deserialize(payload: &mut MessageReader) -> Result<Self> {
         let red_wins = payload.read_u8()?;
         let blu_wins = payload.read_u8()?;
         let state = payload.read_u8()?;
@@ -160,20 +158,21 @@ impl ClientNetworkDeserialize for GamemodeHudArenaFull {
     }
 }
 
-impl ClientNetworkDeserialize for RawControlPoint {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
-    where
-        I: Iterator<Item = u8>,
-        C: ClientNetworkDeserializationContext,
-    {
-        let team = payload
-            .read_u8()?
+/// Reads a team that's sent as -1 when there isn't one
+fn read_optional_team(payload: &mut MessageReader) -> Result<Option<TeamSpawnable>> {
+    match payload.read_u8()? {
+        u8::MAX => Ok(None),
+        team => team
             .try_into()
-            .map_err(|_| Error::PacketPayload)?;
-        let capturing_team = payload
-            .read_u8()?
-            .try_into()
-            .map_err(|_| Error::PacketPayload)?;
+            .map(Some)
+            .map_err(|_| CommonError::Network(Error::PacketPayload)),
+    }
+}
+
+impl ClientNetworkDeserializeHud for RawControlPoint {
+    fn deserialize(payload: &mut MessageReader) -> Result<Self> {
+        let team = read_optional_team(payload)?;
+        let capturing_team = read_optional_team(payload)?;
         let capturing = payload.read_u16()?;
 
         Ok(Self {
@@ -184,12 +183,8 @@ impl ClientNetworkDeserialize for RawControlPoint {
     }
 }
 
-impl ClientNetworkDeserialize for HudKothTimer {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
-    where
-        I: Iterator<Item = u8>,
-        C: ClientNetworkDeserializationContext,
-    {
+impl ClientNetworkDeserializeHud for HudKothTimer {
+    fn deserialize(payload: &mut MessageReader) -> Result<Self> {
         let capture_unlock = payload.read_duration_u16_sec()?;
         let red_timer = payload.read_duration_u16_sec()?;
         let blu_timer = payload.read_duration_u16_sec()?;
@@ -202,17 +197,93 @@ impl ClientNetworkDeserialize for HudKothTimer {
     }
 }
 
+impl ClientNetworkDeserialize for ServerArenaEndround {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let winners = payload
+            .read_u8()?
+            .try_into()
+            .map_err(|_| Error::PacketPayload)?;
+
+        Ok(Self { winners })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerArenaRestart {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ClientNetworkDeserialize for ServerArenaStartround {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ClientNetworkDeserialize for ServerArenaWaitForPlayers {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ClientNetworkDeserialize for ServerBalance {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let player_id = PlayerId::from_u8(payload.read_u8()?);
+
+        Ok(Self { player_id })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerBlueTeamCap {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ClientNetworkDeserialize for ServerBuildSentry {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let player_id = payload.read_u8()?.try_into()?;
+        let position = payload.read_fixed_point_u16_vec2(5.0)?;
+        // Sent as the sentry's signed horizontal scale
+        let looking_left = (payload.read_u8()? as i8).is_negative();
+
+        Ok(Self {
+            player_id,
+            position,
+            looking_left,
+        })
+    }
+}
+
 impl ClientNetworkDeserialize for ServerCaptureUpdate {
-    async fn deserialize<I, C>(payload: &mut I, context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_amount = payload.read_u8()?;
-        let captures = Captures::deserialize(payload, context).await?;
-        context
-            .deserialize_gamemode_state(payload, Self::KIND)
-            .await?;
+        let captures = Captures::deserialize(payload, context)?;
+        context.deserialize_gamemode_state(payload, Self::KIND)?;
 
         Ok(Self {
             player_amount,
@@ -222,26 +293,28 @@ impl ClientNetworkDeserialize for ServerCaptureUpdate {
 }
 
 impl ClientNetworkDeserialize for ServerChangeMap {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let map_name = payload.read_utf8_short_string()?;
         let map_md5 = payload.read_md5()?;
 
-        if map_name.chars().by_ref().all(char::is_alphanumeric) {
-            Err(CommonError::Network(Error::UnsanitizedString))
-        } else {
+        // The name is used to look up the map, so it can't hold a path
+        if map_name
+            .chars()
+            .all(|char| char.is_alphanumeric() || matches!(char, '_' | '-'))
+        {
             Ok(Self { map_name, map_md5 })
+        } else {
+            Err(CommonError::Network(Error::UnsanitizedString))
         }
     }
 }
 
 impl ClientNetworkDeserialize for ServerChatBubble {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         // TODO: What does this byte do?
@@ -256,10 +329,55 @@ impl ClientNetworkDeserialize for ServerChatBubble {
     }
 }
 
+impl ClientNetworkDeserialize for ServerCpCaptured {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let control_point = payload.read_u8()?;
+        let team = payload
+            .read_u8()?
+            .try_into()
+            .map_err(|_| Error::PacketPayload)?;
+
+        let cappers_length = payload.read_u8()?;
+        let cappers = payload.read_list(cappers_length.into(), |payload| {
+            payload.read_u8()?.try_into()
+        })?;
+
+        Ok(Self {
+            control_point,
+            team,
+            cappers,
+        })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerDestroySentry {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let player_id = payload.read_u8()?.try_into()?;
+
+        Ok(Self { player_id })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerDownloadMap {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let length = payload.read_u32()?;
+
+        Ok(Self { length })
+    }
+}
+
 impl ClientNetworkDeserialize for ServerDropIntel {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_id = payload.read_u8()?.try_into()?;
@@ -268,10 +386,23 @@ impl ClientNetworkDeserialize for ServerDropIntel {
     }
 }
 
+impl ClientNetworkDeserialize for ServerGeneratorDestroy {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let team = payload
+            .read_u8()?
+            .try_into()
+            .map_err(|_| Error::PacketPayload)?;
+
+        Ok(Self { team })
+    }
+}
+
 impl ClientNetworkDeserialize for ServerGrabIntel {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_id = payload.read_u8()?.try_into()?;
@@ -281,9 +412,8 @@ impl ClientNetworkDeserialize for ServerGrabIntel {
 }
 
 impl ClientNetworkDeserialize for RawInput {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let key_state = payload.read_u8()?.into();
@@ -299,9 +429,8 @@ impl ClientNetworkDeserialize for RawInput {
 }
 
 impl ClientNetworkDeserialize for RawPlayerInfo {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let translation = payload.read_fixed_point_u16_vec2(5.0)?;
@@ -321,32 +450,40 @@ impl ClientNetworkDeserialize for RawPlayerInfo {
 }
 
 impl ClientNetworkDeserialize for RawAdditionalPlayerInfo {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
-        // TODO: Implement additional player info
-        for _ in 0..9 {
-            payload.next();
-        }
+        let intel = payload.read_bool()?;
+        let intel_recharge = payload.read_u16()?;
+        let class_state = payload.read_array()?;
 
-        Ok(Self {})
+        Ok(Self {
+            intel,
+            intel_recharge,
+            class_state,
+        })
     }
 }
 
 /// TODO: Merge player_length into context
 trait DeserializePlayerUpdateInfo: Sized {
-    async fn deserialize<I, C>(payload: &mut I, context: &C, player_length: u8) -> Result<Self>
+    fn deserialize<C>(
+        payload: &mut MessageReader,
+        context: &mut C,
+        player_length: u8,
+    ) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext;
 }
 
 impl DeserializePlayerUpdateInfo for PlayerUpdateInfo {
-    async fn deserialize<I, C>(payload: &mut I, context: &C, player_length: u8) -> Result<Self>
+    fn deserialize<C>(
+        payload: &mut MessageReader,
+        context: &mut C,
+        player_length: u8,
+    ) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let kills = payload.read_u8()?;
@@ -364,13 +501,13 @@ impl DeserializePlayerUpdateInfo for PlayerUpdateInfo {
         let rewards = payload.read_utf8_long_string()?;
 
         let non_current_players = player_length.saturating_sub(1);
-        let dominations = payload.take(non_current_players as usize).collect();
+        let dominations = payload.read_bytes(non_current_players.into())?.to_vec();
 
         let character_present = payload.read_bool()?;
         let character = if character_present {
-            let input = RawInput::deserialize(payload, context).await?;
-            let player_info = RawPlayerInfo::deserialize(payload, context).await?;
-            let additional_into = RawAdditionalPlayerInfo::deserialize(payload, context).await?;
+            let input = RawInput::deserialize(payload, context)?;
+            let player_info = RawPlayerInfo::deserialize(payload, context)?;
+            let additional_into = RawAdditionalPlayerInfo::deserialize(payload, context)?;
             Some((input, player_info, additional_into))
         } else {
             None
@@ -397,9 +534,8 @@ impl DeserializePlayerUpdateInfo for PlayerUpdateInfo {
 }
 
 impl ClientNetworkDeserialize for RawIntel {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let position = payload.read_fixed_point_u16_vec2(5.0)?;
@@ -411,47 +547,51 @@ impl ClientNetworkDeserialize for RawIntel {
     }
 }
 
+impl ClientNetworkDeserialize for RawMovingPlatform {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let translation = payload.read_fixed_point_u16_vec2(5.0)?;
+        let returning = payload.read_bool()?;
+
+        Ok(Self {
+            translation,
+            returning,
+        })
+    }
+}
+
 impl ClientNetworkDeserialize for ServerFullUpdate {
-    async fn deserialize<I, C>(payload: &mut I, context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let team_death_match_invulnerability_ticks = payload.read_u16()?;
         let player_length = payload.read_u8()?;
 
-        let mut player_info = Vec::with_capacity(player_length as usize);
-
-        for _ in 0..player_length {
-            player_info.push(PlayerUpdateInfo::deserialize(payload, context, player_length).await?);
-        }
+        let player_info = payload.read_list(player_length.into(), |payload| {
+            PlayerUpdateInfo::deserialize(payload, context, player_length)
+        })?;
 
-        // TODO: Moving platform
-        //payload.next();
-        //payload.next();
-        //payload.next();
-        //payload.next();
-        //payload.next();
+        let moving_platforms_length = context.current_map_moving_platforms_length()?;
+        let moving_platforms = payload.read_list(moving_platforms_length.into(), |payload| {
+            RawMovingPlatform::deserialize(payload, context)
+        })?;
 
         let red_intel_length = payload.read_u16()?;
-        let mut red_intel = Vec::with_capacity(red_intel_length as usize);
-
-        for _ in 0..red_intel_length {
-            red_intel.push(RawIntel::deserialize(payload, context).await?);
-        }
+        let red_intel = payload.read_list(red_intel_length.into(), |payload| {
+            RawIntel::deserialize(payload, context)
+        })?;
 
         let blu_intel_length = payload.read_u16()?;
-        let mut blu_intel = Vec::with_capacity(blu_intel_length as usize);
-
-        for _ in 0..blu_intel_length {
-            blu_intel.push(RawIntel::deserialize(payload, context).await?);
-        }
+        let blu_intel = payload.read_list(blu_intel_length.into(), |payload| {
+            RawIntel::deserialize(payload, context)
+        })?;
 
         let capture_limit = payload.read_u8()?;
-        let captures = Captures::deserialize(payload, context).await?;
-        context
-            .deserialize_gamemode_state(payload, Self::KIND)
-            .await?;
+        let captures = Captures::deserialize(payload, context)?;
+        context.deserialize_gamemode_state(payload, Self::KIND)?;
 
         let scout_limit = payload.read_u8()?;
         let soldier_limit = payload.read_u8()?;
@@ -467,6 +607,7 @@ impl ClientNetworkDeserialize for ServerFullUpdate {
         Ok(Self {
             team_death_match_invulnerability_ticks,
             player_info,
+            moving_platforms,
             red_intel,
             blu_intel,
             capture_limit,
@@ -486,9 +627,8 @@ impl ClientNetworkDeserialize for ServerFullUpdate {
 }
 
 impl ClientNetworkDeserialize for ServerHello {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let server_name = payload.read_utf8_short_string()?;
@@ -496,22 +636,22 @@ impl ClientNetworkDeserialize for ServerHello {
 
         let map_md5 = payload.read_md5()?;
 
-        let _plugins_amounts = payload.next().ok_or(Error::UnexpectedEOF)?;
-        let _plugins_raw = payload.read_utf8_long_string()?;
+        let plugins_required = payload.read_bool()?;
+        let plugins = parse_plugin_list(&payload.read_utf8_long_string()?)?;
 
         Ok(Self {
             server_name,
             map_name,
             map_md5,
-            plugins: Vec::new(),
+            plugins_required,
+            plugins,
         })
     }
 }
 
 impl ClientNetworkDeserialize for ServerIncompatibleProtocol {
-    async fn deserialize<I, C>(_payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         Ok(Self)
@@ -519,33 +659,28 @@ impl ClientNetworkDeserialize for ServerIncompatibleProtocol {
 }
 
 impl ClientNetworkDeserialize for ServerInputState {
-    async fn deserialize<I, C>(payload: &mut I, context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let character_length = payload.read_u8()?;
-        let mut inputs = Vec::with_capacity(character_length as usize);
-
-        for _ in 0..character_length {
+        let inputs = payload.read_list(character_length.into(), |payload| {
             let has_character = payload.read_bool()?;
-            let input = if has_character {
-                Some(RawInput::deserialize(payload, context).await?)
-            } else {
-                None
-            };
 
-            inputs.push(input);
-        }
+            if has_character {
+                RawInput::deserialize(payload, context).map(Some)
+            } else {
+                Ok(None)
+            }
+        })?;
 
         Ok(Self { inputs })
     }
 }
 
 impl ClientNetworkDeserialize for ServerJoinUpdate {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let client_player_id = payload.read_u8()?.try_into()?;
@@ -557,10 +692,70 @@ impl ClientNetworkDeserialize for ServerJoinUpdate {
     }
 }
 
+impl ClientNetworkDeserialize for ServerKick {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let reason = payload
+            .read_u8()?
+            .try_into()
+            .map_err(|_| Error::PacketPayload)?;
+
+        Ok(Self { reason })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerKickBadPluginPacket {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ClientNetworkDeserialize for ServerKickMultiClient {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ClientNetworkDeserialize for ServerKickName {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ClientNetworkDeserialize for ServerMapEnd {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let next_map = payload.read_utf8_short_string()?;
+        let winners = payload
+            .read_u8()?
+            .try_into()
+            .map_err(|_| Error::PacketPayload)?;
+        let map_area = payload.read_u8()?;
+
+        Ok(Self {
+            next_map,
+            winners,
+            map_area,
+        })
+    }
+}
+
 impl ClientNetworkDeserialize for ServerMessageString {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let message = payload.read_utf8_short_string()?;
@@ -569,9 +764,8 @@ impl ClientNetworkDeserialize for ServerMessageString {
 }
 
 impl ClientNetworkDeserialize for ServerOmnom {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let _unknown = payload.read_u8()?;
@@ -580,9 +774,8 @@ impl ClientNetworkDeserialize for ServerOmnom {
 }
 
 impl ClientNetworkDeserialize for ServerPasswordRequest {
-    async fn deserialize<I, C>(_payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         Ok(Self)
@@ -590,9 +783,17 @@ impl ClientNetworkDeserialize for ServerPasswordRequest {
 }
 
 impl ClientNetworkDeserialize for ServerPasswordWrong {
-    async fn deserialize<I, C>(_payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ClientNetworkDeserialize for ServerPing {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         Ok(Self)
@@ -600,9 +801,8 @@ impl ClientNetworkDeserialize for ServerPasswordWrong {
 }
 
 impl ClientNetworkDeserialize for ServerPlayerChangeClass {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_id = payload.read_u8()?.try_into()?;
@@ -619,9 +819,8 @@ impl ClientNetworkDeserialize for ServerPlayerChangeClass {
 }
 
 impl ClientNetworkDeserialize for ServerPlayerChangeName {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_id = payload.read_u8()?.try_into()?;
@@ -632,9 +831,8 @@ impl ClientNetworkDeserialize for ServerPlayerChangeName {
 }
 
 impl ClientNetworkDeserialize for ServerPlayerChangeTeam {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_id = payload.read_u8()?.try_into()?;
@@ -651,9 +849,8 @@ impl ClientNetworkDeserialize for ServerPlayerChangeTeam {
 }
 
 impl ClientNetworkDeserialize for ServerPlayerDeath {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let target = payload.read_u8()?.try_into()?;
@@ -674,9 +871,8 @@ impl ClientNetworkDeserialize for ServerPlayerDeath {
 }
 
 impl ClientNetworkDeserialize for ServerPlayerJoin {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_name = payload.read_utf8_short_string()?;
@@ -686,9 +882,8 @@ impl ClientNetworkDeserialize for ServerPlayerJoin {
 }
 
 impl ClientNetworkDeserialize for ServerPlayerLeave {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_id = payload.read_u8()?.try_into()?;
@@ -697,9 +892,8 @@ impl ClientNetworkDeserialize for ServerPlayerLeave {
 }
 
 impl ClientNetworkDeserialize for ServerPlayerSpawn {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_id = payload.read_u8()?.try_into()?;
@@ -714,45 +908,68 @@ impl ClientNetworkDeserialize for ServerPlayerSpawn {
     }
 }
 
+impl ClientNetworkDeserialize for ServerPluginPacket {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        // The length includes the plugin id
+        let length = payload
+            .read_u16()?
+            .checked_sub(1)
+            .ok_or(Error::PacketPayload)?;
+        let plugin_id = payload.read_u8()?;
+        let payload = payload.read_bytes(length.into())?.to_vec();
+
+        Ok(Self { plugin_id, payload })
+    }
+}
+
 impl ClientNetworkDeserialize for ServerQuickUpdate {
-    async fn deserialize<I, C>(payload: &mut I, context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_length = payload.read_u8()?;
 
-        let mut player_characters = Vec::with_capacity(player_length.into());
+        let player_characters =
+            payload.read_list(player_length.into(), |payload| -> Result<_> {
+                let character_present = payload.read_bool()?;
 
-        for _ in 0..player_length {
-            let character_present = payload.read_bool()?;
-            let character = if character_present {
-                let input = RawInput::deserialize(payload, context).await?;
-                let player_info = RawPlayerInfo::deserialize(payload, context).await?;
+                if character_present {
+                    let input = RawInput::deserialize(payload, context)?;
+                    let player_info = RawPlayerInfo::deserialize(payload, context)?;
 
-                Some((input, player_info))
-            } else {
-                None
-            };
+                    Ok(Some((input, player_info)))
+                } else {
+                    Ok(None)
+                }
+            })?;
 
-            player_characters.push(character);
-        }
+        let moving_platforms_length = context.current_map_moving_platforms_length()?;
+        let moving_platforms = payload.read_list(moving_platforms_length.into(), |payload| {
+            RawMovingPlatform::deserialize(payload, context)
+        })?;
 
-        // TODO: Moving platform
-        //payload.next();
-        //payload.next();
-        //payload.next();
-        //payload.next();
-        //payload.next();
+        Ok(Self {
+            player_characters,
+            moving_platforms,
+        })
+    }
+}
 
-        Ok(Self { player_characters })
+impl ClientNetworkDeserialize for ServerRedTeamCap {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
     }
 }
 
 impl ClientNetworkDeserialize for ServerReserveSlot {
-    async fn deserialize<I, C>(_payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         Ok(Self)
@@ -760,9 +977,8 @@ impl ClientNetworkDeserialize for ServerReserveSlot {
 }
 
 impl ClientNetworkDeserialize for ServerReturnIntel {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let team = payload
@@ -774,10 +990,32 @@ impl ClientNetworkDeserialize for ServerReturnIntel {
     }
 }
 
+impl ClientNetworkDeserialize for ServerRewardChallengeCode {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let challenge = payload.read_array()?;
+
+        Ok(Self { challenge })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerRewardUpdate {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let player_id = payload.read_u8()?.try_into()?;
+        let rewards = payload.read_utf8_long_string()?;
+
+        Ok(Self { player_id, rewards })
+    }
+}
+
 impl ClientNetworkDeserialize for ServerScoreIntel {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_id = payload.read_u8()?.try_into()?;
@@ -786,10 +1024,75 @@ impl ClientNetworkDeserialize for ServerScoreIntel {
     }
 }
 
+impl ClientNetworkDeserialize for ServerSentryPosition {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let player_id = payload.read_u8()?.try_into()?;
+        let position = payload.read_fixed_point_u16_vec2(5.0)?;
+
+        Ok(Self {
+            player_id,
+            position,
+        })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerServerKick {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
 impl ClientNetworkDeserialize for ServerServerFull {
-    async fn deserialize<I, C>(_payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ClientNetworkDeserialize for ServerToggleZoom {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let player_id = payload.read_u8()?.try_into()?;
+
+        Ok(Self { player_id })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerUber {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let player_id = payload.read_u8()?.try_into()?;
+
+        Ok(Self { player_id })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerUberCharged {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
+    where
+        C: ClientNetworkDeserializationContext,
+    {
+        let player_id = payload.read_u8()?.try_into()?;
+
+        Ok(Self { player_id })
+    }
+}
+
+impl ClientNetworkDeserialize for ServerUnlockCp {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         Ok(Self)
@@ -797,9 +1100,8 @@ impl ClientNetworkDeserialize for ServerServerFull {
 }
 
 impl ClientNetworkDeserialize for ServerWeaponFire {
-    async fn deserialize<I, C>(payload: &mut I, _context: &C) -> Result<Self>
+    fn deserialize<C>(payload: &mut MessageReader, _context: &mut C) -> Result<Self>
     where
-        I: Iterator<Item = u8>,
         C: ClientNetworkDeserializationContext,
     {
         let player_id = payload.read_u8()?.try_into()?;
@@ -815,3 +1117,92 @@ impl ClientNetworkDeserialize for ServerWeaponFire {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+
+    /// Reads a CTF match timer as the gamemode state
+    struct FuzzContext;
+
+    impl ClientNetworkDeserializationContext for FuzzContext {
+        fn players_length(&self) -> u8 {
+            0
+        }
+
+        fn deserialize_gamemode_state(
+            &mut self,
+            payload: &mut MessageReader,
+            _kind: PacketKind,
+        ) -> Result<()> {
+            HudMatchTimer::deserialize(payload).map(|_| ())
+        }
+
+        fn current_map_control_points_length(&self) -> Result<u8> {
+            Ok(5)
+        }
+
+        fn current_map_moving_platforms_length(&self) -> Result<u8> {
+            Ok(2)
+        }
+    }
+
+    fn deserialize(bytes: &[u8]) -> (Result<ServerMessageGeneric>, usize) {
+        let mut payload = MessageReader::new(bytes);
+        let message = ServerMessageGeneric::deserialize(&mut payload, &mut FuzzContext);
+
+        (message, payload.consumed())
+    }
+
+    #[test]
+    fn reject_huge_intel_list() {
+        // No invulnerability ticks or players
+        let mut bytes = vec![PacketKind::FullUpdate.into(), 0, 0, 0];
+        // Both of the map's moving platforms
+        bytes.extend([0; 10]);
+        // Red intel length, far past the end of the packet
+        bytes.extend(u16::MAX.to_le_bytes());
+
+        let (message, consumed) = deserialize(&bytes);
+        assert!(matches!(
+            message,
+            Err(CommonError::Network(Error::UnexpectedEOF))
+        ));
+        // Rejected at the intel list rather than anywhere before it
+        assert_eq!(consumed, bytes.len());
+    }
+
+    #[test]
+    fn reject_map_name_path() {
+        let mut bytes = vec![PacketKind::ChangeMap.into()];
+        bytes.push(14);
+        bytes.extend(b"../../settings");
+        // No MD5, as a builtin map
+        bytes.push(0);
+
+        let (message, _) = deserialize(&bytes);
+        assert!(matches!(
+            message,
+            Err(CommonError::Network(Error::UnsanitizedString))
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn fuzz_arbitrary_bytes(bytes in vec(any::<u8>(), 0..1024)) {
+            let (_, consumed) = deserialize(&bytes);
+            prop_assert!(consumed <= bytes.len());
+        }
+
+        #[test]
+        fn fuzz_packet_kind(kind in 0..=u8::from(PacketKind::ReserveSlot), body in vec(any::<u8>(), 0..1024)) {
+            let mut bytes = vec![kind];
+            bytes.extend(body);
+
+            let (_, consumed) = deserialize(&bytes);
+            prop_assert!(consumed <= bytes.len());
+        }
+    }
+}
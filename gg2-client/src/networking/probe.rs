@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use gg2_common::{
+    error::{CommonError, Result},
+    networking::{
+        PacketKind,
+        error::NetworkError,
+        message::{ClientHello, MessageReader, ServerHello, ServerMessageGeneric},
+    },
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    time::Instant,
+};
+
+use super::{
+    connect::{open_stream, with_default_port},
+    message::{
+        ClientNetworkDeserializationContext, ClientNetworkDeserialize, ClientNetworkSerialize,
+    },
+};
+
+/// How a server answered a hello
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProbeResponse {
+    /// The server is open to players
+    Hello(ServerHello),
+    PasswordRequired,
+    IncompatibleProtocol,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeReport {
+    /// The address probed, with the port filled in
+    pub address: String,
+    pub response: ProbeResponse,
+    /// From sending the hello until the answer started arriving
+    pub round_trip: Duration,
+}
+
+/// Asks a server about itself without taking a player slot
+///
+/// The connection is closed once the server answers
+pub async fn probe(address: &str, timeout: Duration) -> Result<ProbeReport> {
+    let address = with_default_port(address);
+
+    tokio::time::timeout(timeout, exchange_hello(&address))
+        .await
+        .unwrap_or_else(|_| Err(NetworkError::ConnectTimeout(address.clone()).into()))
+}
+
+async fn exchange_hello(address: &str) -> Result<ProbeReport> {
+    let mut stream = open_stream(address).await?;
+
+    let mut hello = vec![PacketKind::Hello.into()];
+    ClientHello::default().serialize(&mut hello).await?;
+
+    let sent = Instant::now();
+    stream
+        .write_all(&hello)
+        .await
+        .map_err(|_| NetworkError::PacketSend)?;
+
+    let mut received = Vec::new();
+    let mut buffer = [0; 1024];
+    let mut round_trip = None;
+
+    loop {
+        let length = stream
+            .read(&mut buffer)
+            .await
+            .map_err(|error| NetworkError::Connection(error, address.to_string()))?;
+
+        if length == 0 {
+            return Err(NetworkError::ChannelClosed.into());
+        }
+
+        let round_trip = *round_trip.get_or_insert_with(|| sent.elapsed());
+        received.extend(&buffer[..length]);
+
+        let response = match ServerMessageGeneric::deserialize(
+            &mut MessageReader::new(&received),
+            &mut ProbeContext,
+        ) {
+            Ok(ServerMessageGeneric::Hello(message)) => ProbeResponse::Hello(message),
+            Ok(ServerMessageGeneric::PasswordRequest(_)) => ProbeResponse::PasswordRequired,
+            Ok(ServerMessageGeneric::IncompatibleProtocol(_)) => {
+                ProbeResponse::IncompatibleProtocol
+            }
+            Ok(message) => Err(NetworkError::IncorrectMessage(message.into()))?,
+            Err(CommonError::Network(NetworkError::UnexpectedEOF)) => continue,
+            Err(error) => return Err(error),
+        };
+
+        return Ok(ProbeReport {
+            address: address.to_string(),
+            response,
+            round_trip,
+        });
+    }
+}
+
+/// Answers before joining never depend on game state
+struct ProbeContext;
+
+impl ClientNetworkDeserializationContext for ProbeContext {
+    fn players_length(&self) -> u8 {
+        0
+    }
+
+    fn deserialize_gamemode_state(
+        &mut self,
+        _payload: &mut MessageReader,
+        kind: PacketKind,
+    ) -> Result<()> {
+        Err(NetworkError::IncorrectMessage(kind).into())
+    }
+
+    fn current_map_control_points_length(&self) -> Result<u8> {
+        Err(CommonError::MapUnloaded)
+    }
+
+    fn current_map_moving_platforms_length(&self) -> Result<u8> {
+        Err(CommonError::MapUnloaded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use gg2_common::networking::message::MessageWriter;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// Answers one hello with the given bytes
+    async fn serve(answer: Vec<u8>) -> String {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let mut hello = [0; 17];
+            stream.read_exact(&mut hello).await.unwrap();
+            assert_eq!(hello[0], u8::from(PacketKind::Hello));
+
+            stream.write_all(&answer).await.unwrap();
+        });
+
+        address
+    }
+
+    #[tokio::test]
+    async fn probe_hello() {
+        let mut answer = vec![PacketKind::Hello.into()];
+        answer.write_utf8_short_string(&"Ferris' Server".parse().unwrap());
+        answer.write_utf8_short_string(&"ctf_eiger".parse().unwrap());
+        answer.write_md5(None);
+        answer.write_bool(false);
+        answer.write_utf8_long_string(&"".parse().unwrap());
+
+        let report = probe(&serve(answer).await, Duration::from_secs(5))
+            .await
+            .unwrap();
+
+        let ProbeResponse::Hello(hello) = report.response else {
+            panic!("Expected hello");
+        };
+        assert_eq!(&*hello.server_name, "Ferris' Server");
+        assert_eq!(&*hello.map_name, "ctf_eiger");
+        assert!(hello.plugins.is_empty());
+    }
+
+    #[tokio::test]
+    async fn probe_password() {
+        let address = serve(vec![PacketKind::PasswordRequest.into()]).await;
+        let report = probe(&address, Duration::from_secs(5)).await.unwrap();
+
+        assert_eq!(report.address, address);
+        assert_eq!(report.response, ProbeResponse::PasswordRequired);
+    }
+
+    #[tokio::test]
+    async fn probe_closed() {
+        let address = serve(Vec::new()).await;
+
+        assert!(matches!(
+            probe(&address, Duration::from_secs(5)).await,
+            Err(CommonError::Network(NetworkError::ChannelClosed))
+        ));
+    }
+}
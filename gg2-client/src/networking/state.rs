@@ -1,10 +1,145 @@
-#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+use std::fmt::Display;
+
+use gg2_common::networking::{KickReason, message::ServerMessageGeneric};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum NetworkingState {
-    #[default]
-    Disconnected,
+    /// Holds why the last connection ended, if there was one
+    Disconnected(Option<DisconnectReason>),
     AttemptingConnection,
     AwaitingHello,
+    /// Downloading the server's map before joining
+    DownloadingMap,
     ReserveSlot,
     PlayerJoining,
     InGame,
 }
+
+impl Default for NetworkingState {
+    #[inline]
+    fn default() -> Self {
+        Self::Disconnected(None)
+    }
+}
+
+/// Why the client is no longer connected to a server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisconnectReason {
+    /// The client chose to leave
+    Left,
+    /// The connection closed without the server giving a reason
+    ConnectionClosed,
+    /// The server couldn't be resolved or connected to
+    Unreachable,
+    /// The server stopped sending anything
+    TimedOut,
+    /// Kicked or banned by an admin
+    Kicked,
+    Refused(KickReason),
+    ServerFull,
+    IncompatibleProtocol,
+    PasswordWrong,
+    /// The server's map couldn't be downloaded
+    MapDownload,
+    /// The server sent a packet for a plugin no handler claimed
+    UnknownPlugin,
+    /// The server requires plugins the client has no handler for
+    UnsupportedPlugins,
+}
+
+impl DisconnectReason {
+    /// The reason a message ends the connection, if it does
+    pub fn from_message(message: &ServerMessageGeneric) -> Option<Self> {
+        match message {
+            ServerMessageGeneric::ServerKick(_) => Some(Self::Kicked),
+            ServerMessageGeneric::Kick(message) => Some(Self::Refused(message.reason)),
+            ServerMessageGeneric::KickName(_) => Some(Self::Refused(KickReason::Name)),
+            ServerMessageGeneric::KickBadPluginPacket(_) => {
+                Some(Self::Refused(KickReason::BadPluginPacket))
+            }
+            ServerMessageGeneric::KickMultiClient(_) => {
+                Some(Self::Refused(KickReason::MultiClient))
+            }
+            ServerMessageGeneric::ServerFull(_) => Some(Self::ServerFull),
+            ServerMessageGeneric::IncompatibleProtocol(_) => Some(Self::IncompatibleProtocol),
+            ServerMessageGeneric::PasswordWrong(_) => Some(Self::PasswordWrong),
+            _ => None,
+        }
+    }
+
+    /// Whether reconnecting could get past the problem
+    pub const fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::ConnectionClosed | Self::Unreachable | Self::TimedOut
+        )
+    }
+}
+
+impl Display for DisconnectReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Left => write!(f, "Left the server"),
+            Self::ConnectionClosed => write!(f, "Lost connection to the server"),
+            Self::Unreachable => write!(f, "Couldn't reach the server"),
+            Self::TimedOut => write!(f, "The server stopped responding"),
+            Self::Kicked => write!(f, "Kicked from the server"),
+            Self::Refused(KickReason::Name) => write!(f, "Kicked for using a disallowed name"),
+            Self::Refused(KickReason::BadPluginPacket) => {
+                write!(f, "Kicked for sending an invalid plugin packet")
+            }
+            Self::Refused(KickReason::MultiClient) => {
+                write!(f, "Kicked for too many connections from your address")
+            }
+            Self::ServerFull => write!(f, "The server is full"),
+            Self::IncompatibleProtocol => write!(f, "The server uses an incompatible protocol"),
+            Self::PasswordWrong => write!(f, "The server password is wrong"),
+            Self::MapDownload => write!(f, "Failed to download the server's map"),
+            Self::UnknownPlugin => write!(f, "The server sent a packet for an unknown plugin"),
+            Self::UnsupportedPlugins => {
+                write!(f, "The server requires plugins this client doesn't support")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gg2_common::networking::message::{
+        ServerKick, ServerKickMultiClient, ServerKickName, ServerMessageString,
+    };
+
+    use super::*;
+
+    #[test]
+    fn kicks_have_reasons() {
+        assert_eq!(
+            DisconnectReason::from_message(&ServerMessageGeneric::KickName(ServerKickName)),
+            Some(DisconnectReason::Refused(KickReason::Name))
+        );
+        assert_eq!(
+            DisconnectReason::from_message(&ServerMessageGeneric::Kick(ServerKick {
+                reason: KickReason::MultiClient
+            })),
+            DisconnectReason::from_message(&ServerMessageGeneric::KickMultiClient(
+                ServerKickMultiClient
+            ))
+        );
+        assert_eq!(
+            DisconnectReason::from_message(&ServerMessageGeneric::MessageString(
+                ServerMessageString {
+                    message: "Hi".parse().unwrap()
+                }
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn only_connection_problems_retry() {
+        assert!(DisconnectReason::TimedOut.is_retryable());
+        assert!(DisconnectReason::ConnectionClosed.is_retryable());
+        assert!(!DisconnectReason::Left.is_retryable());
+        assert!(!DisconnectReason::Refused(KickReason::Name).is_retryable());
+    }
+}
@@ -22,4 +22,8 @@ pub enum CommonError {
     Network(#[from] crate::networking::error::NetworkError),
     #[error("Expected map to be loaded")]
     MapUnloaded,
+    #[error("Message doesn't apply to the current gamemode")]
+    GamemodeMismatch,
+    #[error("Failed to lookup control point with index: {0}")]
+    ControlPointLookup(u8),
 }
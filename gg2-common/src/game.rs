@@ -1,3 +1,4 @@
 pub mod control_point;
 pub mod generator;
 pub mod intel;
+pub mod moving_platform;
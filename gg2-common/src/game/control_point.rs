@@ -1,8 +1,10 @@
 use crate::player::team::TeamSpawnable;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct RawControlPoint {
-    pub team: TeamSpawnable,
-    pub capturing_team: TeamSpawnable,
+    /// The team that owns the point
+    /// Neutral if none
+    pub team: Option<TeamSpawnable>,
+    pub capturing_team: Option<TeamSpawnable>,
     pub capturing: u16,
 }
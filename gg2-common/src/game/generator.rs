@@ -1,5 +1,19 @@
-#[derive(Debug, Clone)]
+/// The health a generator starts a round with
+pub const GENERATOR_MAX_HEALTH: u16 = 4000;
+/// The health a generator's shield starts a round with
+pub const GENERATOR_MAX_SHIELD_HEALTH: u16 = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct RawGenerator {
     pub health: u16,
     pub shield_health: u16,
 }
+
+impl Default for RawGenerator {
+    fn default() -> Self {
+        Self {
+            health: GENERATOR_MAX_HEALTH,
+            shield_health: GENERATOR_MAX_SHIELD_HEALTH,
+        }
+    }
+}
@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use glam::Vec2;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RawIntel {
     pub position: Vec2,
     pub recharge_time: Duration,
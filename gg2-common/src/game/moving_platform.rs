@@ -0,0 +1,8 @@
+use glam::Vec2;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RawMovingPlatform {
+    pub translation: Vec2,
+    /// Heading back to where it started
+    pub returning: bool,
+}
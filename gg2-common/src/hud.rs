@@ -2,20 +2,20 @@ use std::time::Duration;
 
 use crate::game::{control_point::RawControlPoint, generator::RawGenerator};
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct HudMatchTimer {
     pub total: Duration,
     pub left: Duration,
 }
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct HudKothTimer {
     pub capture_unlock: Duration,
     pub red_timer: Duration,
     pub blu_timer: Duration,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct GamemodeHudArenaFull {
     pub red_wins: u8,
     pub blu_wins: u8,
@@ -1,5 +1,4 @@
 #![feature(macro_metavar_expr_concat)]
-#![feature(iter_next_chunk)]
 pub mod chat;
 pub mod damage;
 pub mod error;
@@ -10,11 +10,36 @@ pub struct MapData {
     /// A slice of all red spawn groups with list of spawn positions
     pub red_spawns: [Vec<Vec2>; 5],
     pub gamemode: Gamemode,
-    // TODO: Parse control points properly
-    pub control_points_length: u8,
+    /// Control point positions in the order the server sends their state
+    pub control_points: Vec<Vec2>,
+    /// In the order the server sends their state
+    pub moving_platforms: Vec<MovingPlatformPath>,
+}
+
+/// Where a moving platform travels between
+#[derive(Debug, Clone, PartialEq)]
+pub struct MovingPlatformPath {
+    pub start: Vec2,
+    pub end: Vec2,
+    /// Multiplies the platform's size
+    pub scale: Vec2,
+    /// Pixels per frame at 30 frames a second
+    pub speed: u8,
 }
 
 impl MapData {
+    /// Never more than `u8::MAX`, as maps with more fail to load
+    #[inline]
+    pub fn control_points_length(&self) -> u8 {
+        self.control_points.len() as u8
+    }
+
+    /// Never more than `u8::MAX`, as maps with more fail to load
+    #[inline]
+    pub fn moving_platforms_length(&self) -> u8 {
+        self.moving_platforms.len() as u8
+    }
+
     pub fn get_spawn_position(
         &self,
         team: &TeamSpawnable,
@@ -45,6 +45,27 @@ impl MoveBox {
 }
 
 #[derive(Debug, Deserialize, Serialize)]
+pub struct MovingPlatform {
+    #[serde(flatten)]
+    pub transform: EntityTransform,
+    /// How far the platform moves from where it starts before turning around
+    #[serde(rename = "xtravel", default)]
+    pub travel_x: i32,
+    #[serde(rename = "ytravel", default)]
+    pub travel_y: i32,
+    /// Pixels per frame at 30 frames a second
+    #[serde(default = "MovingPlatform::default_speed")]
+    pub speed: u8,
+}
+
+impl MovingPlatform {
+    #[inline]
+    fn default_speed() -> u8 {
+        2
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct EntityPosition {
     pub x: u32,
     pub y: u32,
@@ -204,7 +225,7 @@ pub enum MapEntity {
         foreground: Foreground,
     },
     #[serde(rename = "moving_platform")]
-    MovingPlatform,
+    MovingPlatform(MovingPlatform),
     #[serde(untagged)]
     Custom {
         #[serde(rename = "type")]
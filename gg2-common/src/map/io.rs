@@ -2,9 +2,13 @@ use std::str::FromStr;
 
 use crate::{gamemode::Gamemode, player::team::TeamSpawnable};
 
-use super::{data::MapData, entity::MapEntity};
+use super::{
+    data::{MapData, MovingPlatformPath},
+    entity::MapEntity,
+};
 use error::{MapIoError, Result};
 use glam::Vec2;
+use md5::{Digest, Md5};
 
 pub mod error;
 
@@ -113,18 +117,10 @@ impl MapData {
         let mut blu_spawns = <[Vec<Vec2>; 5]>::default();
         let mut red_spawns = <[Vec<Vec2>; 5]>::default();
 
-        let mut control_points_length = 0;
+        let control_points = Self::control_points(&entities)?;
+        let moving_platforms = Self::moving_platforms(&entities)?;
 
         for entity in entities {
-            match entity {
-                MapEntity::ControlPoint1(_) => control_points_length += 1,
-                MapEntity::ControlPoint2(_) => control_points_length += 1,
-                MapEntity::ControlPoint3(_) => control_points_length += 1,
-                MapEntity::ControlPoint4(_) => control_points_length += 1,
-                MapEntity::ControlPoint5(_) => control_points_length += 1,
-                _ => (),
-            }
-
             let (group, position, team) = match entity {
                 MapEntity::BluSpawn0(position) => (0, position, TeamSpawnable::Blu),
                 MapEntity::BluSpawn1(position) => (1, position, TeamSpawnable::Blu),
@@ -152,10 +148,72 @@ impl MapData {
             blu_spawns,
             red_spawns,
             gamemode,
-            control_points_length,
+            control_points,
+            moving_platforms,
         })
     }
 
+    /// Kept in map order, which is the order the server sends them in
+    fn moving_platforms(entities: &[MapEntity]) -> Result<Vec<MovingPlatformPath>> {
+        let moving_platforms = entities
+            .iter()
+            .filter_map(|entity| match entity {
+                MapEntity::MovingPlatform(platform) => {
+                    let start = Vec2::from(platform.transform.position);
+                    let travel = Vec2::new(platform.travel_x as f32, platform.travel_y as f32);
+
+                    Some(MovingPlatformPath {
+                        start,
+                        end: start + travel,
+                        scale: Vec2::new(
+                            platform.transform.scale.x_scale,
+                            platform.transform.scale.y_scale,
+                        ),
+                        speed: platform.speed,
+                    })
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if moving_platforms.len() > usize::from(u8::MAX) {
+            return Err(MapIoError::MovingPlatforms);
+        }
+
+        Ok(moving_platforms)
+    }
+
+    /// Orders control points by their number, with dual KOTH's red point first
+    fn control_points(entities: &[MapEntity]) -> Result<Vec<Vec2>> {
+        let mut control_points = entities
+            .iter()
+            .filter_map(|entity| match entity {
+                MapEntity::ControlPoint1(position)
+                | MapEntity::ArenaControlPoint(position)
+                | MapEntity::KothControlPoint(position)
+                | MapEntity::KothRedControlPoint(position) => Some((0, *position)),
+                MapEntity::ControlPoint2(position) | MapEntity::KothBlueControlPoint(position) => {
+                    Some((1, *position))
+                }
+                MapEntity::ControlPoint3(position) => Some((2, *position)),
+                MapEntity::ControlPoint4(position) => Some((3, *position)),
+                MapEntity::ControlPoint5(position) => Some((4, *position)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if control_points.len() > usize::from(u8::MAX) {
+            return Err(MapIoError::ControlPoints);
+        }
+
+        control_points.sort_by_key(|(index, _)| *index);
+
+        Ok(control_points
+            .into_iter()
+            .map(|(_, position)| position.into())
+            .collect())
+    }
+
     fn read_entities<'a, I: Iterator<Item = &'a str>>(
         data_lines: &mut I,
     ) -> Result<Vec<MapEntity>> {
@@ -199,6 +257,11 @@ impl MapData {
     }
 }
 
+/// The hash servers identify a map file by
+pub fn map_md5(buf: &[u8]) -> u128 {
+    u128::from_be_bytes(Md5::digest(buf).into())
+}
+
 /// Converts an ASCII character to a 6-bit mask.
 fn get_bitmask_from_character(character: u8) -> u8 {
     character.saturating_sub(BITMASK_SHIFT) & BITMASK_MAX_BYTE
@@ -206,7 +269,46 @@ fn get_bitmask_from_character(character: u8) -> u8 {
 
 #[cfg(test)]
 mod tests {
+    use proptest::{collection::vec, prelude::*};
+
     use super::*;
+    use crate::map::entity::{EntityPosition, EntityScale, EntityTransform, MovingPlatform};
+
+    /// Wraps map data in a PNG the same way the map editor does
+    fn map_png(data_text: String) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        let mut encoder = png::Encoder::new(&mut buffer, 1, 1);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder
+            .add_ztxt_chunk(DATA_HEADER_KEYWORD.to_string(), data_text)
+            .unwrap();
+
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[0]).unwrap();
+        writer.finish().unwrap();
+
+        buffer
+    }
+
+    /// Lines that look like map data
+    fn data_line() -> impl Strategy<Value = String> {
+        prop_oneof![
+            Just(MapDataTag::Entities.to_string()),
+            Just(MapDataTag::EndEntities.to_string()),
+            Just(MapDataTag::WalkMask.to_string()),
+            Just(MapDataTag::EndWalkMask.to_string()),
+            "[0-9]{1,12}",
+            "[ -~]{0,64}",
+        ]
+    }
+
+    #[test]
+    fn md5_hex_order() {
+        assert_eq!(
+            map_md5(b"gg2"),
+            u128::from_str_radix("7d7f7e971ca8152804ad89b891fba0b4", 16).unwrap()
+        );
+    }
 
     #[test]
     fn data_tag_entities() {
@@ -236,4 +338,83 @@ mod tests {
     fn data_tag_error() {
         assert!(MapDataTag::from_str("super secret tag").is_err());
     }
+
+    #[test]
+    fn load_missing_data() {
+        assert!(matches!(
+            MapData::load_from_memory(&map_png(String::new())),
+            Err(MapIoError::DataTagMissing(MapDataTag::Entities))
+        ));
+    }
+
+    #[test]
+    fn control_point_order() {
+        let position = |x| EntityPosition { x, y: 0 };
+        let entities = [
+            MapEntity::ControlPoint3(position(30)),
+            MapEntity::ControlPoint1(position(10)),
+            MapEntity::RedSpawn0(position(0)),
+            MapEntity::ControlPoint2(position(20)),
+        ];
+
+        assert_eq!(
+            MapData::control_points(&entities).unwrap(),
+            [
+                Vec2::new(10.0, 0.0),
+                Vec2::new(20.0, 0.0),
+                Vec2::new(30.0, 0.0)
+            ]
+        );
+
+        let entities = [
+            MapEntity::KothBlueControlPoint(position(20)),
+            MapEntity::KothRedControlPoint(position(10)),
+        ];
+
+        assert_eq!(
+            MapData::control_points(&entities).unwrap(),
+            [Vec2::new(10.0, 0.0), Vec2::new(20.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn moving_platform_paths() {
+        let entities = [
+            MapEntity::RedSpawn0(EntityPosition { x: 0, y: 0 }),
+            MapEntity::MovingPlatform(MovingPlatform {
+                transform: EntityTransform {
+                    position: EntityPosition { x: 100, y: 200 },
+                    scale: EntityScale {
+                        x_scale: 2.0,
+                        y_scale: 1.0,
+                    },
+                },
+                travel_x: -50,
+                travel_y: 0,
+                speed: 3,
+            }),
+        ];
+
+        assert_eq!(
+            MapData::moving_platforms(&entities).unwrap(),
+            [MovingPlatformPath {
+                start: Vec2::new(100.0, 200.0),
+                end: Vec2::new(50.0, 200.0),
+                scale: Vec2::new(2.0, 1.0),
+                speed: 3,
+            }]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn fuzz_load_arbitrary_bytes(bytes in vec(any::<u8>(), 0..1024)) {
+            let _ = MapData::load_from_memory(&bytes);
+        }
+
+        #[test]
+        fn fuzz_load_data_text(lines in vec(data_line(), 0..16)) {
+            let _ = MapData::load_from_memory(&map_png(lines.join("\n")));
+        }
+    }
 }
@@ -27,4 +27,8 @@ pub enum MapIoError {
     DataTagMissing(MapDataTag),
     #[error("Couldn't determine map gamemode")]
     Gamemode,
+    #[error("Map has too many control points")]
+    ControlPoints,
+    #[error("Map has too many moving platforms")]
+    MovingPlatforms,
 }
@@ -4,6 +4,7 @@ use uuid::{Uuid, uuid};
 
 pub mod error;
 pub mod message;
+pub mod plugin;
 
 /// The protocol UUID that is sent on a client to server Hello message
 pub const PROTOCOL_UUID: Uuid = uuid!("b31c2209-4256-9a19-d0ef-c71c5373bd75");
@@ -72,6 +73,19 @@ pub enum PacketKind {
     ReserveSlot = 60,
 }
 
+/// Why the server kicked a client
+/// Sent with `Kick` as the matching packet kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum KickReason {
+    /// The player's name isn't allowed
+    Name = 39,
+    /// The client sent a plugin packet the server doesn't know
+    BadPluginPacket = 56,
+    /// Too many clients are connected from the same address
+    MultiClient = 59,
+}
+
 pub trait AsPacketKind {
     fn as_packet_kind(&self) -> PacketKind;
 }
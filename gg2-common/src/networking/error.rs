@@ -14,6 +14,12 @@ pub enum NetworkError {
     Listen(std::io::Error),
     #[error("An error occured when trying to connect to '{1}': {0}")]
     Connection(std::io::Error, String),
+    #[error("Failed to resolve '{1}': {0}")]
+    Resolve(std::io::Error, String),
+    #[error("No addresses found for '{0}'")]
+    NoAddress(String),
+    #[error("Timed out connecting to '{0}'")]
+    ConnectTimeout(String),
     #[error("No data was found in the packet")]
     PacketEmpty,
     #[error("Failed to parse packet kind: {0}")]
@@ -24,6 +30,8 @@ pub enum NetworkError {
     PacketPayload,
     #[error("Failed to serialize packet payload: {0}")]
     StringLength(std::num::TryFromIntError),
+    #[error("Failed to serialize packet list: {0}")]
+    ListLength(std::num::TryFromIntError),
     #[error("Unsanitized string")]
     UnsanitizedString,
     #[error("Message not allowed at this time: {0:?}")]
@@ -18,53 +18,91 @@ pub trait GGMessage: Sync + Send {
     const KIND: PacketKind;
 }
 
-pub trait MessageReader {
-    fn read_u8(&mut self) -> Result<u8>;
+/// A cursor over the bytes of a received message
+#[derive(Debug, Clone)]
+pub struct MessageReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
 
-    fn read_u16(&mut self) -> Result<u16>;
+impl<'a> MessageReader<'a> {
+    #[inline]
+    #[must_use]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
 
-    fn read_u32(&mut self) -> Result<u32>;
+    /// How many bytes have been read so far
+    #[inline]
+    #[must_use]
+    pub fn consumed(&self) -> usize {
+        self.position
+    }
 
-    fn read_bool(&mut self) -> Result<bool>;
+    /// The bytes that haven't been read yet
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.bytes[self.position..]
+    }
 
-    fn read_fixed_point_u8(&mut self, scale: f32) -> Result<f32>;
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.remaining().is_empty()
+    }
 
-    fn read_fixed_point_u8_vec2(&mut self, scale: f32) -> Result<Vec2>;
+    /// Reads exactly `length` bytes, failing if the message ends first
+    pub fn read_bytes(&mut self, length: usize) -> Result<&'a [u8]> {
+        let bytes = self.remaining().get(..length).ok_or(Error::UnexpectedEOF)?;
+        self.position += length;
 
-    fn read_fixed_point_u16(&mut self, scale: f32) -> Result<f32>;
+        Ok(bytes)
+    }
 
-    fn read_fixed_point_u16_vec2(&mut self, scale: f32) -> Result<Vec2>;
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        self.read_bytes(N)
+            .map(|bytes| bytes.try_into().expect("Length was checked"))
+    }
 
-    fn read_utf8_short_string(&mut self) -> Result<GGStringShort>;
+    /// Reads `length` items, failing early if there aren't enough bytes left
+    /// so a bogus length can't allocate more than the message holds
+    ///
+    /// Every item must take at least one byte
+    pub fn read_list<T, E: From<Error>>(
+        &mut self,
+        length: usize,
+        mut read_item: impl FnMut(&mut Self) -> std::result::Result<T, E>,
+    ) -> std::result::Result<Vec<T>, E> {
+        if length > self.remaining().len() {
+            return Err(Error::UnexpectedEOF.into());
+        }
 
-    fn read_utf8_long_string(&mut self) -> Result<GGStringLong>;
+        let mut items = Vec::with_capacity(length);
 
-    fn read_md5(&mut self) -> Result<Option<u128>>;
+        for _ in 0..length {
+            items.push(read_item(self)?);
+        }
 
-    fn read_duration_u16_sec(&mut self) -> Result<Duration>;
-}
+        Ok(items)
+    }
 
-impl<T> MessageReader for T
-where
-    T: Iterator<Item = u8>,
-{
-    fn read_u8(&mut self) -> Result<u8> {
-        self.next().ok_or(Error::UnexpectedEOF)
+    #[inline]
+    pub fn read_u8(&mut self) -> Result<u8> {
+        self.read_array().map(u8::from_le_bytes)
     }
 
-    fn read_u16(&mut self) -> Result<u16> {
-        self.next_chunk()
-            .map(u16::from_le_bytes)
-            .map_err(|_| Error::UnexpectedEOF)
+    #[inline]
+    pub fn read_u16(&mut self) -> Result<u16> {
+        self.read_array().map(u16::from_le_bytes)
     }
 
-    fn read_u32(&mut self) -> Result<u32> {
-        self.next_chunk()
-            .map(u32::from_le_bytes)
-            .map_err(|_| Error::UnexpectedEOF)
+    #[inline]
+    pub fn read_u32(&mut self) -> Result<u32> {
+        self.read_array().map(u32::from_le_bytes)
     }
 
-    fn read_bool(&mut self) -> Result<bool> {
+    pub fn read_bool(&mut self) -> Result<bool> {
         self.read_u8().and_then(|value| match value {
             0 => Ok(false),
             1 => Ok(true),
@@ -73,85 +111,91 @@ where
     }
 
     #[inline]
-    fn read_fixed_point_u8(&mut self, scale: f32) -> Result<f32> {
+    pub fn read_fixed_point_u8(&mut self, scale: f32) -> Result<f32> {
         self.read_u8().map(|value| value as f32 / scale)
     }
 
-    fn read_fixed_point_u8_vec2(&mut self, scale: f32) -> Result<Vec2> {
+    pub fn read_fixed_point_u8_vec2(&mut self, scale: f32) -> Result<Vec2> {
         let x = self.read_fixed_point_u8(scale)?;
         let y = self.read_fixed_point_u8(scale)?;
         Ok(Vec2::new(x, y))
     }
 
     #[inline]
-    fn read_fixed_point_u16(&mut self, scale: f32) -> Result<f32> {
+    pub fn read_fixed_point_u16(&mut self, scale: f32) -> Result<f32> {
         self.read_u16().map(|value| value as f32 / scale)
     }
 
-    fn read_fixed_point_u16_vec2(&mut self, scale: f32) -> Result<Vec2> {
+    pub fn read_fixed_point_u16_vec2(&mut self, scale: f32) -> Result<Vec2> {
         let x = self.read_fixed_point_u16(scale)?;
         let y = self.read_fixed_point_u16(scale)?;
         Ok(Vec2::new(x, y))
     }
 
-    fn read_utf8_short_string(&mut self) -> Result<GGStringShort> {
+    fn read_str(&mut self, length: usize) -> Result<&'a str> {
+        let bytes = self.read_bytes(length)?;
+        std::str::from_utf8(bytes).map_err(|_| Error::PacketPayload)
+    }
+
+    pub fn read_utf8_short_string(&mut self) -> Result<GGStringShort> {
         let length = self.read_u8()?;
-        let bytes = self.take(length as usize).collect();
-        GGStringShort::from_utf8(bytes).map_err(|_| Error::PacketPayload)
+        self.read_str(length.into())?.parse()
     }
 
-    fn read_utf8_long_string(&mut self) -> Result<GGStringLong> {
+    pub fn read_utf8_long_string(&mut self) -> Result<GGStringLong> {
         let length = self.read_u16()?;
-        let bytes = self.take(length as usize).collect();
-        GGStringLong::from_utf8(bytes).map_err(|_| Error::PacketPayload)
+        self.read_str(length.into())?.parse()
     }
 
-    fn read_md5(&mut self) -> Result<Option<u128>> {
+    pub fn read_md5(&mut self) -> Result<Option<u128>> {
         let length = self.read_u8()?;
         match length {
             0 => Ok(None),
             // The length of a 128-bit hex string
             32 => {
-                let hex_bytes = self.take(32).collect();
-                let hex_string = String::from_utf8(hex_bytes).map_err(|_| Error::PacketPayload)?;
-                let hash_bytes = hex::decode(hex_string).map_err(|_| Error::PacketPayload)?;
-
-                assert_eq!(hash_bytes.len(), 16);
-
-                Ok(Some(
-                    hash_bytes
-                        .into_iter()
-                        .rev()
-                        .enumerate()
-                        .map(|(i, byte)| (byte as u128) << (i * 8))
-                        .sum(),
-                ))
+                let hex_bytes = self.read_bytes(32)?;
+                let mut hash_bytes = [0; 16];
+                hex::decode_to_slice(hex_bytes, &mut hash_bytes)
+                    .map_err(|_| Error::PacketPayload)?;
+
+                Ok(Some(u128::from_be_bytes(hash_bytes)))
             }
             _ => Err(Error::PacketPayload),
         }
     }
 
-    fn read_duration_u16_sec(&mut self) -> Result<Duration> {
+    pub fn read_duration_u16_sec(&mut self) -> Result<Duration> {
         self.read_u16().map(u64::from).map(Duration::from_secs)
     }
 }
 
 pub trait MessageWriter {
-    fn write_utf8_short_string(&mut self, value: &GGStringShort);
-
     fn write_u8(&mut self, value: u8);
 
     fn write_u16(&mut self, value: u16);
 
+    fn write_u32(&mut self, value: u32);
+
+    fn write_bool(&mut self, value: bool);
+
+    fn write_fixed_point_u8(&mut self, value: f32, scale: f32);
+
+    fn write_fixed_point_u8_vec2(&mut self, value: Vec2, scale: f32);
+
     fn write_fixed_point_u16(&mut self, value: f32, scale: f32);
+
+    fn write_fixed_point_u16_vec2(&mut self, value: Vec2, scale: f32);
+
+    fn write_utf8_short_string(&mut self, value: &GGStringShort);
+
+    fn write_utf8_long_string(&mut self, value: &GGStringLong);
+
+    fn write_md5(&mut self, value: Option<u128>);
+
+    fn write_duration_u16_sec(&mut self, value: Duration);
 }
 
 impl MessageWriter for Vec<u8> {
-    fn write_utf8_short_string(&mut self, value: &GGStringShort) {
-        self.push(value.len());
-        self.extend(value.bytes());
-    }
-
     #[inline]
     fn write_u8(&mut self, value: u8) {
         self.push(value);
@@ -161,10 +205,60 @@ impl MessageWriter for Vec<u8> {
         self.extend(value.to_le_bytes());
     }
 
+    fn write_u32(&mut self, value: u32) {
+        self.extend(value.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_bool(&mut self, value: bool) {
+        self.write_u8(value.into());
+    }
+
+    #[inline]
+    fn write_fixed_point_u8(&mut self, value: f32, scale: f32) {
+        self.write_u8((value * scale) as u8);
+    }
+
+    fn write_fixed_point_u8_vec2(&mut self, value: Vec2, scale: f32) {
+        self.write_fixed_point_u8(value.x, scale);
+        self.write_fixed_point_u8(value.y, scale);
+    }
+
     #[inline]
     fn write_fixed_point_u16(&mut self, value: f32, scale: f32) {
         self.write_u16((value * scale) as u16);
     }
+
+    fn write_fixed_point_u16_vec2(&mut self, value: Vec2, scale: f32) {
+        self.write_fixed_point_u16(value.x, scale);
+        self.write_fixed_point_u16(value.y, scale);
+    }
+
+    fn write_utf8_short_string(&mut self, value: &GGStringShort) {
+        self.push(value.len());
+        self.extend(value.bytes());
+    }
+
+    fn write_utf8_long_string(&mut self, value: &GGStringLong) {
+        self.write_u16(value.len());
+        self.extend(value.bytes());
+    }
+
+    fn write_md5(&mut self, value: Option<u128>) {
+        match value {
+            Some(hash) => {
+                let hex_string = hex::encode(hash.to_be_bytes());
+                self.write_u8(hex_string.len() as u8);
+                self.extend(hex_string.bytes());
+            }
+            None => self.write_u8(0),
+        }
+    }
+
+    #[inline]
+    fn write_duration_u16_sec(&mut self, value: Duration) {
+        self.write_u16(value.as_secs() as u16);
+    }
 }
 
 #[cfg(test)]
@@ -173,14 +267,15 @@ mod tests {
 
     #[test]
     fn read_string_short() {
-        let mut data = [4, b't', b'e', b's', b't'].into_iter();
+        let mut data = MessageReader::new(&[4, b't', b'e', b's', b't']);
         let parsed = data.read_utf8_short_string().unwrap().to_string();
         assert_eq!(parsed, "test");
+        assert_eq!(data.consumed(), 5);
     }
 
     #[test]
     fn read_string_long() {
-        let mut data = [4, 0, b'l', b'o', b'n', b'g'].into_iter();
+        let mut data = MessageReader::new(&[4, 0, b'l', b'o', b'n', b'g']);
         let parsed = data.read_utf8_long_string().unwrap().to_string();
         assert_eq!(parsed, "long");
     }
@@ -189,14 +284,77 @@ mod tests {
     fn read_md5_string() {
         let mut data = vec![32];
         data.extend("e0cae13971b1ba6a8eef49cbcfc944bf".as_bytes());
-        let parsed = data.into_iter().read_md5().unwrap();
+        let parsed = MessageReader::new(&data).read_md5().unwrap();
         assert_eq!(parsed, Some(298800483114597941956032572434422514879));
     }
 
+    #[test]
+    fn read_truncated() {
+        let mut data = MessageReader::new(&[4, b't', b'e']);
+        assert!(matches!(
+            data.read_utf8_short_string(),
+            Err(Error::UnexpectedEOF)
+        ));
+        assert_eq!(data.remaining(), [b't', b'e']);
+
+        let mut data = vec![32];
+        data.extend("e0cae139".as_bytes());
+        assert!(matches!(
+            MessageReader::new(&data).read_md5(),
+            Err(Error::UnexpectedEOF)
+        ));
+    }
+
+    #[test]
+    fn read_integers() {
+        let mut data = MessageReader::new(&[1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(data.read_u8().unwrap(), 1);
+        assert_eq!(data.read_u16().unwrap(), 0x0302);
+        assert_eq!(data.read_u32().unwrap(), 0x07060504);
+        assert!(data.is_empty());
+        assert!(matches!(data.read_u8(), Err(Error::UnexpectedEOF)));
+    }
+
+    #[test]
+    fn read_list_too_long() {
+        let mut data = MessageReader::new(&[u8::MAX, 1, 2]);
+        let length = data.read_u8().unwrap();
+        let result = data.read_list(length.into(), MessageReader::read_u8);
+
+        assert!(matches!(result, Err(Error::UnexpectedEOF)));
+        assert_eq!(data.remaining(), [1, 2]);
+    }
+
+    #[test]
+    fn read_md5_invalid() {
+        let mut data = vec![32];
+        data.extend("not a hex string, but 32 bytes!!".as_bytes());
+        assert!(matches!(
+            MessageReader::new(&data).read_md5(),
+            Err(Error::PacketPayload)
+        ));
+    }
+
     #[test]
     fn read_md5_empty() {
-        let mut data = vec![0].into_iter();
-        let parsed = data.read_md5().unwrap();
+        let parsed = MessageReader::new(&[0]).read_md5().unwrap();
         assert_eq!(parsed, None);
     }
+
+    #[test]
+    fn write_md5_string() {
+        let mut data = Vec::new();
+        data.write_md5(Some(298800483114597941956032572434422514879));
+
+        let mut expected = vec![32];
+        expected.extend("e0cae13971b1ba6a8eef49cbcfc944bf".as_bytes());
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn write_md5_empty() {
+        let mut data = Vec::new();
+        data.write_md5(None);
+        assert_eq!(data, [0]);
+    }
 }
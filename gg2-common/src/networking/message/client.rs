@@ -9,7 +9,7 @@ use super::GGStringShort;
 
 macro_rules! generic_message {
     ($name: ident {$($case: ident),+$(,)?}) => {
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq)]
         pub enum $name {
             $($case(${concat(Client, $case)})),+,
         }
@@ -25,15 +25,40 @@ macro_rules! generic_message {
 }
 
 generic_message!(ClientMessageGeneric {
+    ClientSettings,
+    DownloadMap,
     Hello,
     InputState,
+    Ping,
     PlayerChangeClass,
     PlayerChangeTeam,
     PlayerJoin,
+    PluginPacket,
     ReserveSlot,
+    RewardChallengeResponse,
+    RewardRequest,
 });
 
-#[derive(Debug, Clone)]
+/// The client's preferences for how the server treats it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClientClientSettings {
+    /// Skip the respawn queue when the server allows it
+    pub queue_jump: bool,
+}
+
+impl GGMessage for ClientClientSettings {
+    const KIND: PacketKind = PacketKind::ClientSettings;
+}
+
+/// Asks for the server's current map
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientDownloadMap;
+
+impl GGMessage for ClientDownloadMap {
+    const KIND: PacketKind = PacketKind::DownloadMap;
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ClientHello {
     pub protocol: Uuid,
 }
@@ -50,7 +75,7 @@ impl Default for ClientHello {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ClientInputState {
     pub input: RawInput,
 }
@@ -59,7 +84,22 @@ impl GGMessage for ClientInputState {
     const KIND: PacketKind = PacketKind::InputState;
 }
 
-#[derive(Debug, Clone)]
+/// The reply to a password request
+/// Sent bare, without a packet kind, so it isn't a [`ClientMessageGeneric`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientPassword {
+    pub password: GGStringShort,
+}
+
+/// Measures latency; answered with a ping, or itself the answer to one
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientPing;
+
+impl GGMessage for ClientPing {
+    const KIND: PacketKind = PacketKind::Ping;
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ClientPlayerChangeClass {
     pub class: ClassGeneric,
 }
@@ -68,7 +108,7 @@ impl GGMessage for ClientPlayerChangeClass {
     const KIND: PacketKind = PacketKind::PlayerChangeClass;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ClientPlayerChangeTeam {
     pub team: Team,
 }
@@ -77,14 +117,26 @@ impl GGMessage for ClientPlayerChangeTeam {
     const KIND: PacketKind = PacketKind::PlayerChangeTeam;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ClientPlayerJoin;
 
 impl GGMessage for ClientPlayerJoin {
     const KIND: PacketKind = PacketKind::PlayerJoin;
 }
 
-#[derive(Debug, Clone)]
+/// A packet for one of the server's plugins
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientPluginPacket {
+    /// The id claimed by the plugin
+    pub plugin_id: u8,
+    pub payload: Vec<u8>,
+}
+
+impl GGMessage for ClientPluginPacket {
+    const KIND: PacketKind = PacketKind::PluginPacket;
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ClientReserveSlot {
     pub player_name: GGStringShort,
 }
@@ -92,3 +144,24 @@ pub struct ClientReserveSlot {
 impl GGMessage for ClientReserveSlot {
     const KIND: PacketKind = PacketKind::ReserveSlot;
 }
+
+/// The client's reward key applied to a reward challenge
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientRewardChallengeResponse {
+    /// HMAC-MD5 of the challenge followed by the server's IP address
+    pub response: [u8; 16],
+}
+
+impl GGMessage for ClientRewardChallengeResponse {
+    const KIND: PacketKind = PacketKind::RewardChallengeResponse;
+}
+
+/// Claims the rewards given to a name
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientRewardRequest {
+    pub reward_name: GGStringShort,
+}
+
+impl GGMessage for ClientRewardRequest {
+    const KIND: PacketKind = PacketKind::RewardRequest;
+}
@@ -1,8 +1,8 @@
 use crate::{
     chat::bubble::ChatBubble,
     damage::source::DamageSource,
-    game::intel::RawIntel,
-    networking::{AsPacketKind, PacketKind},
+    game::{intel::RawIntel, moving_platform::RawMovingPlatform},
+    networking::{AsPacketKind, KickReason, PacketKind, plugin::ServerPlugin},
     player::{
         PlayerId, RawAdditionalPlayerInfo, RawInput, RawPlayerInfo,
         class::ClassGeneric,
@@ -15,7 +15,7 @@ use super::{GGMessage, GGStringLong, GGStringShort};
 
 macro_rules! generic_message {
     ($name: ident {$($case: ident),+$(,)?}) => {
-        #[derive(Debug, Clone)]
+        #[derive(Debug, Clone, PartialEq)]
         pub enum $name {
             $($case(${concat(Server, $case)})),+,
         }
@@ -43,55 +43,125 @@ generic_message!(ServerMessageGeneric {
     QuickUpdate,
     PlayerDeath,
     ServerFull,
-    //RedTeamCap = 12,
-    //BlueTeamCap = 13,
-    //MapEnd = 14,
+    RedTeamCap,
+    BlueTeamCap,
+    MapEnd,
     ChatBubble,
-    //BuildSentry = 16,
-    //DestroySentry = 17,
-    //Balance = 18,
+    BuildSentry,
+    DestroySentry,
+    Balance,
     GrabIntel,
     ScoreIntel,
     DropIntel,
-    //UberCharged = 22,
-    //Uber = 23,
+    UberCharged,
+    Uber,
     Omnom,
     PasswordRequest,
     PasswordWrong,
     CaptureUpdate,
-    //CpCaptured = 30,
+    CpCaptured,
     PlayerChangeName,
-    //GeneratorDestroy = 32,
-    //ArenaWaitForPlayers = 33,
-    //ArenaEndround = 34,
-    //ArenaRestart = 35,
-    //UnlockCp = 36,
-    //ServerKick = 37,
-    //Kick = 38,
-    //KickName = 39,
-    //ArenaStartround = 40,
-    //ToggleZoom = 41,
+    GeneratorDestroy,
+    ArenaWaitForPlayers,
+    ArenaEndround,
+    ArenaRestart,
+    UnlockCp,
+    ServerKick,
+    Kick,
+    KickName,
+    ArenaStartround,
+    ToggleZoom,
     ReturnIntel,
     IncompatibleProtocol,
     JoinUpdate,
-    //DownloadMap = 45,
-    //SentryPosition = 46,
-    //RewardUpdate = 47,
+    DownloadMap,
+    SentryPosition,
+    RewardUpdate,
     //RewardRequest = 50,
-    //RewardChallengeCode = 51,
+    RewardChallengeCode,
     //RewardChallengeResponse = 52,
     MessageString,
     WeaponFire,
-    //PluginPacket = 55,
-    //KickBadPluginPacket = 56,
-    //Ping = 57,
-    //ClientSettings = 58,
-    //KickMultiClient = 59,
+    PluginPacket,
+    KickBadPluginPacket,
+    Ping,
+    // ClientSettings = 58 is only sent by clients
+    KickMultiClient,
     ReserveSlot,
 });
 
+/// An arena round ended
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerArenaEndround {
+    /// The team that won the round
+    /// Spectator if the round ended in a stalemate
+    pub winners: Team,
+}
+
+impl GGMessage for ServerArenaEndround {
+    const KIND: PacketKind = PacketKind::ArenaEndround;
+}
+
+/// The next arena round is starting
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerArenaRestart;
+
+impl GGMessage for ServerArenaRestart {
+    const KIND: PacketKind = PacketKind::ArenaRestart;
+}
+
+/// Enough players joined for an arena round to start
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerArenaStartround;
+
+impl GGMessage for ServerArenaStartround {
+    const KIND: PacketKind = PacketKind::ArenaStartround;
+}
+
+/// Arena is waiting for enough players to start a round
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerArenaWaitForPlayers;
+
+impl GGMessage for ServerArenaWaitForPlayers {
+    const KIND: PacketKind = PacketKind::ArenaWaitForPlayers;
+}
+
+/// A player was moved to the other team to even them out
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerBalance {
+    /// The player who was moved
+    /// None warns that a player is about to be moved
+    pub player_id: Option<PlayerId>,
+}
+
+impl GGMessage for ServerBalance {
+    const KIND: PacketKind = PacketKind::Balance;
+}
+
+/// Blu captured an intel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerBlueTeamCap;
+
+impl GGMessage for ServerBlueTeamCap {
+    const KIND: PacketKind = PacketKind::BlueTeamCap;
+}
+
+/// An engineer built a sentry
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerBuildSentry {
+    /// The engineer who owns the sentry
+    pub player_id: PlayerId,
+    pub position: Vec2,
+    /// Whether the sentry faces left
+    pub looking_left: bool,
+}
+
+impl GGMessage for ServerBuildSentry {
+    const KIND: PacketKind = PacketKind::BuildSentry;
+}
+
 /// Updates the client about captures
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerCaptureUpdate {
     /// The amount of players on the server
     pub player_amount: u8,
@@ -104,7 +174,7 @@ impl GGMessage for ServerCaptureUpdate {
 }
 
 /// The server is changing maps
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerChangeMap {
     /// The new map
     pub map_name: GGStringShort,
@@ -117,7 +187,7 @@ impl GGMessage for ServerChangeMap {
     const KIND: PacketKind = PacketKind::ChangeMap;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerChatBubble {
     pub bubble: ChatBubble,
 }
@@ -126,9 +196,58 @@ impl GGMessage for ServerChatBubble {
     const KIND: PacketKind = PacketKind::ChatBubble;
 }
 
+/// A control point was captured
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerCpCaptured {
+    /// The point's index in the map's control points
+    pub control_point: u8,
+    /// The team that now owns the point
+    pub team: TeamSpawnable,
+    /// The players credited with the capture
+    pub cappers: Vec<PlayerId>,
+}
+
+impl GGMessage for ServerCpCaptured {
+    const KIND: PacketKind = PacketKind::CpCaptured;
+}
+
+/// A player's sentry was destroyed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerDestroySentry {
+    /// The engineer who owned the sentry
+    pub player_id: PlayerId,
+}
+
+impl GGMessage for ServerDestroySentry {
+    const KIND: PacketKind = PacketKind::DestroySentry;
+}
+
+/// The start of a map download
+/// The map's PNG follows raw, outside of any message
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerDownloadMap {
+    /// How many bytes of the map follow
+    pub length: u32,
+}
+
+impl GGMessage for ServerDownloadMap {
+    const KIND: PacketKind = PacketKind::DownloadMap;
+}
+
+/// A team's generator was destroyed, winning the round for the other team
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerGeneratorDestroy {
+    /// The team that owned the generator
+    pub team: TeamSpawnable,
+}
+
+impl GGMessage for ServerGeneratorDestroy {
+    const KIND: PacketKind = PacketKind::GeneratorDestroy;
+}
+
 /// Intel was dropped by a player
 /// Implicitly happens on player death
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerDropIntel {
     /// The player who dropped the intel
     pub player_id: PlayerId,
@@ -139,7 +258,7 @@ impl GGMessage for ServerDropIntel {
 }
 
 /// A player picked up intel
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerGrabIntel {
     /// The player that grabbed the intel
     pub player_id: PlayerId,
@@ -150,7 +269,7 @@ impl GGMessage for ServerGrabIntel {
 }
 
 /// A player has been killed
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerPlayerDeath {
     /// The player that died
     pub target: PlayerId,
@@ -167,7 +286,7 @@ impl GGMessage for ServerPlayerDeath {
 }
 
 /// Stats about the player and optionally the character
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct PlayerUpdateInfo {
     /// How many kills the player has
     pub kills: u8,
@@ -196,11 +315,13 @@ pub struct PlayerUpdateInfo {
 }
 
 /// Update to inform a client about everything at once
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerFullUpdate {
     pub team_death_match_invulnerability_ticks: u16,
     /// A list of all player's update info in ID order
     pub player_info: Vec<PlayerUpdateInfo>,
+    /// All moving platforms in map order
+    pub moving_platforms: Vec<RawMovingPlatform>,
     /// All red intel currently spawned
     pub red_intel: Vec<RawIntel>,
     /// All blu intel currently spawned
@@ -235,7 +356,7 @@ impl GGMessage for ServerFullUpdate {
 }
 
 /// Used to retreive infomation on the server
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerHello {
     /// The server's name
     pub server_name: GGStringShort,
@@ -244,8 +365,9 @@ pub struct ServerHello {
     /// A MD5 hash of the map
     /// Not present when the map is builtin
     pub map_md5: Option<u128>,
-    // TODO: Implement plugin parsing
-    pub plugins: Vec<()>,
+    /// Whether clients must run the server's plugins to join
+    pub plugins_required: bool,
+    pub plugins: Vec<ServerPlugin>,
 }
 
 impl GGMessage for ServerHello {
@@ -253,7 +375,7 @@ impl GGMessage for ServerHello {
 }
 
 /// The server doesn't support the client's network protocol
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerIncompatibleProtocol;
 
 impl GGMessage for ServerIncompatibleProtocol {
@@ -261,7 +383,7 @@ impl GGMessage for ServerIncompatibleProtocol {
 }
 
 /// The inputs of all players
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerInputState {
     /// A list of all player's inputs in ID
     /// None if player doesn't have a character
@@ -273,7 +395,7 @@ impl GGMessage for ServerInputState {
 }
 
 /// Update when the player first joins the server
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerJoinUpdate {
     /// The player id the client will have
     pub client_player_id: PlayerId,
@@ -285,8 +407,58 @@ impl GGMessage for ServerJoinUpdate {
     const KIND: PacketKind = PacketKind::JoinUpdate;
 }
 
+/// The server kicked the client
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerKick {
+    pub reason: KickReason,
+}
+
+impl GGMessage for ServerKick {
+    const KIND: PacketKind = PacketKind::Kick;
+}
+
+/// The client sent a plugin packet the server doesn't know
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerKickBadPluginPacket;
+
+impl GGMessage for ServerKickBadPluginPacket {
+    const KIND: PacketKind = PacketKind::KickBadPluginPacket;
+}
+
+/// Too many clients are connected from the same address
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerKickMultiClient;
+
+impl GGMessage for ServerKickMultiClient {
+    const KIND: PacketKind = PacketKind::KickMultiClient;
+}
+
+/// The player's name isn't allowed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerKickName;
+
+impl GGMessage for ServerKickName {
+    const KIND: PacketKind = PacketKind::KickName;
+}
+
+/// The round is over and the server will change maps
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerMapEnd {
+    /// The map played after this one
+    pub next_map: GGStringShort,
+    /// The team that won the round
+    /// Spectator if the round ended in a stalemate
+    pub winners: Team,
+    /// The area of the map the round ended in
+    pub map_area: u8,
+}
+
+impl GGMessage for ServerMapEnd {
+    const KIND: PacketKind = PacketKind::MapEnd;
+}
+
 /// The server sent a message
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerMessageString {
     /// The server's message
     pub message: GGStringShort,
@@ -297,7 +469,7 @@ impl GGMessage for ServerMessageString {
 }
 
 /// Heavy used the Omnom special ability
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerOmnom;
 
 impl GGMessage for ServerOmnom {
@@ -305,7 +477,7 @@ impl GGMessage for ServerOmnom {
 }
 
 /// The server is requesting a password
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ServerPasswordRequest;
 
 impl GGMessage for ServerPasswordRequest {
@@ -313,15 +485,23 @@ impl GGMessage for ServerPasswordRequest {
 }
 
 /// The password sent to the server was wrong
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct ServerPasswordWrong;
 
 impl GGMessage for ServerPasswordWrong {
     const KIND: PacketKind = PacketKind::PasswordWrong;
 }
 
+/// Measures latency; answered with a ping, or itself the answer to one
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerPing;
+
+impl GGMessage for ServerPing {
+    const KIND: PacketKind = PacketKind::Ping;
+}
+
 /// A player is changing classes
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerPlayerChangeClass {
     /// The player changing their class
     pub player_id: PlayerId,
@@ -334,7 +514,7 @@ impl GGMessage for ServerPlayerChangeClass {
 }
 
 /// A player is changing their name
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerPlayerChangeName {
     /// The player changing their name
     pub player_id: PlayerId,
@@ -342,8 +522,12 @@ pub struct ServerPlayerChangeName {
     pub name: GGStringShort,
 }
 
+impl GGMessage for ServerPlayerChangeName {
+    const KIND: PacketKind = PacketKind::PlayerChangeName;
+}
+
 /// A player is changing their team
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerPlayerChangeTeam {
     /// The player changing teams
     pub player_id: PlayerId,
@@ -356,7 +540,7 @@ impl GGMessage for ServerPlayerChangeTeam {
 }
 
 /// A player has joined the lobby
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerPlayerJoin {
     /// The name of the player that joined
     pub player_name: GGStringShort,
@@ -367,7 +551,7 @@ impl GGMessage for ServerPlayerJoin {
 }
 
 /// A player has left the lobby
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerPlayerLeave {
     /// The player that left
     pub player_id: PlayerId,
@@ -378,7 +562,7 @@ impl GGMessage for ServerPlayerLeave {
 }
 
 /// A player spawned in the world
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerPlayerSpawn {
     /// The player that spawned
     pub player_id: PlayerId,
@@ -392,19 +576,41 @@ impl GGMessage for ServerPlayerSpawn {
     const KIND: PacketKind = PacketKind::PlayerSpawn;
 }
 
+/// A packet for one of the server's plugins
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerPluginPacket {
+    /// The id claimed by the plugin
+    pub plugin_id: u8,
+    pub payload: Vec<u8>,
+}
+
+impl GGMessage for ServerPluginPacket {
+    const KIND: PacketKind = PacketKind::PluginPacket;
+}
+
 /// Update a client with little information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerQuickUpdate {
     /// A list of all player characters in ID order
     pub player_characters: Vec<Option<(RawInput, RawPlayerInfo)>>,
+    /// All moving platforms in map order
+    pub moving_platforms: Vec<RawMovingPlatform>,
 }
 
 impl GGMessage for ServerQuickUpdate {
     const KIND: PacketKind = PacketKind::QuickUpdate;
 }
 
+/// Red captured an intel
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerRedTeamCap;
+
+impl GGMessage for ServerRedTeamCap {
+    const KIND: PacketKind = PacketKind::RedTeamCap;
+}
+
 /// A confirmation that the player has reserved a slot
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerReserveSlot;
 
 impl GGMessage for ServerReserveSlot {
@@ -412,7 +618,7 @@ impl GGMessage for ServerReserveSlot {
 }
 
 /// An intel was returned
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerReturnIntel {
     /// The intel's team
     pub team: TeamSpawnable,
@@ -422,8 +628,30 @@ impl GGMessage for ServerReturnIntel {
     const KIND: PacketKind = PacketKind::ReturnIntel;
 }
 
+/// A challenge the client answers to prove it holds its reward key
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerRewardChallengeCode {
+    pub challenge: [u8; 16],
+}
+
+impl GGMessage for ServerRewardChallengeCode {
+    const KIND: PacketKind = PacketKind::RewardChallengeCode;
+}
+
+/// A player's rewards changed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerRewardUpdate {
+    pub player_id: PlayerId,
+    /// The names of the player's rewards, separated by colons
+    pub rewards: GGStringLong,
+}
+
+impl GGMessage for ServerRewardUpdate {
+    const KIND: PacketKind = PacketKind::RewardUpdate;
+}
+
 /// A player scored intel
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerScoreIntel {
     /// The player that scored the intel
     pub player_id: PlayerId,
@@ -433,16 +661,76 @@ impl GGMessage for ServerScoreIntel {
     const KIND: PacketKind = PacketKind::ScoreIntel;
 }
 
+/// A sentry moved after being built
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerSentryPosition {
+    /// The engineer who owns the sentry
+    pub player_id: PlayerId,
+    pub position: Vec2,
+}
+
+impl GGMessage for ServerSentryPosition {
+    const KIND: PacketKind = PacketKind::SentryPosition;
+}
+
+/// An admin kicked the client
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerServerKick;
+
+impl GGMessage for ServerServerKick {
+    const KIND: PacketKind = PacketKind::ServerKick;
+}
+
 /// The server isn't accepting more players
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerServerFull;
 
 impl GGMessage for ServerServerFull {
     const KIND: PacketKind = PacketKind::ServerFull;
 }
 
+/// A sniper zoomed in or out
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerToggleZoom {
+    pub player_id: PlayerId,
+}
+
+impl GGMessage for ServerToggleZoom {
+    const KIND: PacketKind = PacketKind::ToggleZoom;
+}
+
+/// A medic deployed their ubercharge
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerUber {
+    /// The medic who deployed it
+    pub player_id: PlayerId,
+}
+
+impl GGMessage for ServerUber {
+    const KIND: PacketKind = PacketKind::Uber;
+}
+
+/// A medic's ubercharge is full
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerUberCharged {
+    /// The medic who charged it
+    pub player_id: PlayerId,
+}
+
+impl GGMessage for ServerUberCharged {
+    const KIND: PacketKind = PacketKind::UberCharged;
+}
+
+/// Locked control points can now be captured
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerUnlockCp;
+
+impl GGMessage for ServerUnlockCp {
+    const KIND: PacketKind = PacketKind::UnlockCp;
+}
+
 /// A player fired a weapon
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerWeaponFire {
     /// The player who fired; must have a character
     pub player_id: PlayerId,
@@ -0,0 +1,100 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::error::{NetworkError as Error, Result};
+
+const PLUGIN_SEPARATOR: char = ',';
+const HASH_SEPARATOR: char = '@';
+/// The length of a 128-bit hex string
+const HASH_LENGTH: usize = 32;
+
+/// A plugin run by the server, as listed in its hello
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ServerPlugin {
+    pub name: String,
+    /// A MD5 hash of the plugin, which doubles as its version
+    pub md5: u128,
+}
+
+impl FromStr for ServerPlugin {
+    type Err = Error;
+
+    /// Parses a plugin in the form of `name@md5`
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, hash) = s.split_once(HASH_SEPARATOR).ok_or(Error::PacketPayload)?;
+
+        if name.is_empty() || hash.len() != HASH_LENGTH {
+            return Err(Error::PacketPayload);
+        }
+
+        let md5 = u128::from_str_radix(hash, 16).map_err(|_| Error::PacketPayload)?;
+
+        Ok(Self {
+            name: name.to_string(),
+            md5,
+        })
+    }
+}
+
+impl Display for ServerPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{HASH_SEPARATOR}{:032x}", self.name, self.md5)
+    }
+}
+
+/// Parses the comma separated plugins sent in a hello
+pub fn parse_plugin_list(list: &str) -> Result<Vec<ServerPlugin>> {
+    list.split(PLUGIN_SEPARATOR)
+        .filter(|plugin| !plugin.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+pub fn format_plugin_list(plugins: &[ServerPlugin]) -> String {
+    plugins
+        .iter()
+        .map(ServerPlugin::to_string)
+        .collect::<Vec<_>>()
+        .join(&PLUGIN_SEPARATOR.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list() {
+        let list =
+            "kothtimer@e0cae13971b1ba6a8eef49cbcfc944bf,HatPack@0000000000000000000000000000002a";
+        let plugins = parse_plugin_list(list).unwrap();
+
+        assert_eq!(
+            plugins,
+            [
+                ServerPlugin {
+                    name: "kothtimer".to_string(),
+                    md5: 298800483114597941956032572434422514879,
+                },
+                ServerPlugin {
+                    name: "HatPack".to_string(),
+                    md5: 42,
+                },
+            ]
+        );
+        assert_eq!(format_plugin_list(&plugins), list);
+        assert!(parse_plugin_list("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn reject_malformed_plugin() {
+        for plugin in [
+            "kothtimer",
+            "@e0cae13971b1ba6a8eef49cbcfc944bf",
+            "kothtimer@e0ca",
+        ] {
+            assert!(
+                matches!(plugin.parse::<ServerPlugin>(), Err(Error::PacketPayload)),
+                "Parsed {plugin:?}"
+            );
+        }
+    }
+}
@@ -5,6 +5,7 @@ use glam::Vec2;
 use crate::error::{CommonError as Error, Result};
 
 pub mod class;
+pub mod reward;
 pub mod team;
 
 #[repr(transparent)]
@@ -107,7 +108,7 @@ impl From<KeyState> for u8 {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct RawInput {
     pub key_state: KeyState,
     pub aim_direction: u16,
@@ -122,7 +123,7 @@ impl RawInput {
     }
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct RawPlayerInfo {
     pub translation: Vec2,
     pub velocity: Vec2,
@@ -132,5 +133,13 @@ pub struct RawPlayerInfo {
     pub move_status: u8,
 }
 
-#[derive(Debug, Clone)]
-pub struct RawAdditionalPlayerInfo {}
+/// Character state only sent with full updates
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RawAdditionalPlayerInfo {
+    /// Carrying the other team's intel
+    pub intel: bool,
+    /// Ticks until the character can pick up intel again
+    pub intel_recharge: u16,
+    /// Read with [`class::ClassState::decode`] once the character's class is known
+    pub class_state: [u8; class::CLASS_STATE_LENGTH],
+}
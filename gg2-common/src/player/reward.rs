@@ -0,0 +1,67 @@
+use std::{collections::BTreeSet, fmt::Display};
+
+const REWARD_SEPARATOR: char = ':';
+
+/// The cosmetic rewards a player has unlocked
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rewards(BTreeSet<String>);
+
+impl Rewards {
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains(name)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+impl From<&str> for Rewards {
+    /// Parses reward names separated by colons
+    fn from(value: &str) -> Self {
+        Self(
+            value
+                .split(REWARD_SEPARATOR)
+                .filter(|name| !name.is_empty())
+                .map(str::to_string)
+                .collect(),
+        )
+    }
+}
+
+impl Display for Rewards {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, name) in self.iter().enumerate() {
+            if index > 0 {
+                write!(f, "{REWARD_SEPARATOR}")?;
+            }
+
+            f.write_str(name)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rewards() {
+        let rewards = Rewards::from("TopHat::Badge:");
+
+        assert!(rewards.contains("TopHat"));
+        assert!(rewards.contains("Badge"));
+        assert!(!rewards.contains(""));
+        assert_eq!(rewards.to_string(), "Badge:TopHat");
+        assert!(Rewards::from("").is_empty());
+    }
+}
@@ -45,6 +45,17 @@ pub enum TeamSpawnable {
     Blu,
 }
 
+impl TeamSpawnable {
+    /// The team playing against this one
+    #[inline]
+    pub const fn opponent(self) -> Self {
+        match self {
+            Self::Red => Self::Blu,
+            Self::Blu => Self::Red,
+        }
+    }
+}
+
 impl TryFrom<&Team> for TeamSpawnable {
     type Error = Error;
 
@@ -73,7 +84,7 @@ pub enum TeamChoice {
 }
 
 /// The server's captures
-#[derive(Debug, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Captures {
     /// Red's total captures in a game
     pub red_captures: u8,
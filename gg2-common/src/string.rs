@@ -16,16 +16,11 @@ macro_rules! string_type {
         pub struct $name(String);
 
         impl $name {
-            // Inline to avoid assert
-            #[inline(always)]
-            #[doc = concat!("Panics if length is greater than ", $length)]
-            pub fn from_utf8(
-                vec: Vec<u8>,
-            ) -> std::result::Result<Self, std::string::FromUtf8Error> {
-                let value = String::from_utf8(vec)?;
-                let length = value.len();
-                assert!(length <= $length_type::MAX as usize);
-                Ok(Self(value))
+            #[doc = concat!("Fails if the bytes aren't UTF-8 or are longer than ", $length)]
+            pub fn from_utf8(vec: Vec<u8>) -> Result<Self> {
+                String::from_utf8(vec)
+                    .map_err(|_| Error::PacketPayload)?
+                    .try_into()
             }
 
             pub fn len(&self) -> $length_type {
@@ -92,3 +87,25 @@ macro_rules! string_type {
 
 string_type!(GGStringLong, 65535, u16);
 string_type!(GGStringShort, 255, u8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_utf8_too_long() {
+        assert!(matches!(
+            GGStringShort::from_utf8(vec![b'a'; 256]),
+            Err(Error::StringLength(_))
+        ));
+        assert!(GGStringLong::from_utf8(vec![b'a'; 256]).is_ok());
+    }
+
+    #[test]
+    fn from_utf8_invalid() {
+        assert!(matches!(
+            GGStringShort::from_utf8(vec![0xFF, 0xFE]),
+            Err(Error::PacketPayload)
+        ));
+    }
+}
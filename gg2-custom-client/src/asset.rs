@@ -39,19 +39,47 @@ impl AssetServer {
         Ok(tokio::fs::read_to_string(path).await?)
     }
 
-    // TODO: Check map MD5
+    /// Finds a map matching the server's hash, looking through downloaded maps first
+    ///
+    /// Maps without a hash are builtin and only come from the loaded packs
+    pub async fn find_map(
+        &self,
+        id: &ResourceId,
+        md5: Option<u128>,
+        map_cache: &MapCache,
+    ) -> Result<Option<Vec<u8>>, AssetError> {
+        if let Some(md5) = md5
+            && let Some(map_buffer) = map_cache.load(md5).await?
+        {
+            return Ok(Some(map_buffer));
+        }
+
+        let Some(base_path) = self.maps.get(id) else {
+            return Ok(None);
+        };
+
+        let path = id.as_path(base_path.as_ref().clone(), AssetType::Map);
+        let map_buffer = Self::load_asset(path).await?;
+
+        match md5 {
+            Some(md5) if map_md5(&map_buffer) != md5 => {
+                debug!("Map {id} doesn't match the server's copy");
+                Ok(None)
+            }
+            _ => Ok(Some(map_buffer)),
+        }
+    }
+
     pub async fn load_map(
         &self,
         id: &ResourceId,
+        md5: Option<u128>,
+        map_cache: &MapCache,
     ) -> Result<(ImageBufferRGBA8, MapData), AssetError> {
-        let base_path = self
-            .maps
-            .get(id)
-            .ok_or_else(|| AssetError::Unloaded(AssetType::Map.to_string(), id.clone()))?
-            .as_ref()
-            .clone();
-        let path = id.as_path(base_path, AssetType::Map);
-        let map_buffer = Self::load_asset(path).await?;
+        let map_buffer = self
+            .find_map(id, md5, map_cache)
+            .await?
+            .ok_or_else(|| AssetError::Unloaded(AssetType::Map.to_string(), id.clone()))?;
 
         let map_data = MapData::load_from_memory(&map_buffer)?;
         let image =
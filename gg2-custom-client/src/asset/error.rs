@@ -20,4 +20,8 @@ pub enum AssetError {
     Resource(#[from] ResourceError),
     #[error("{0}")]
     Map(#[from] MapIoError),
+    #[error("Downloaded map's hash, {found:032x}, doesn't match {expected:032x}")]
+    MapHash { expected: u128, found: u128 },
+    #[error("Server's map is too large to download: {0} bytes")]
+    MapTooLarge(u32),
 }
@@ -58,6 +58,12 @@ pub enum SpriteContextCondition {
         #[serde(default)]
         quote: Option<SpriteContextBranch>,
     },
+    Invulnerable {
+        #[serde(default)]
+        invulnerable: Option<SpriteContextBranch>,
+        #[serde(default)]
+        vulnerable: Option<SpriteContextBranch>,
+    },
 }
 
 impl SpriteContextCondition {
@@ -95,6 +101,16 @@ impl SpriteContextCondition {
                 ClassGeneric::Pyro => pyro,
                 ClassGeneric::Quote => quote,
             }),
+            Self::Invulnerable {
+                invulnerable,
+                vulnerable,
+            } => renderable.get_invulnerable().map(|is_invulnerable| {
+                if is_invulnerable {
+                    invulnerable
+                } else {
+                    vulnerable
+                }
+            }),
         }
         .and_then(Option::as_ref)
         .and_then(|branch| branch.evaluate(renderable))
@@ -135,6 +151,10 @@ pub trait SpriteRenderable {
     fn get_class(&self) -> Option<ClassGeneric> {
         None
     }
+
+    fn get_invulnerable(&self) -> Option<bool> {
+        None
+    }
 }
 
 #[cfg(test)]
@@ -207,4 +227,46 @@ mod tests {
 
         assert_eq!(sprite_raw, expected_raw);
     }
+
+    #[test]
+    fn serialize_team_invulnerable() {
+        let invulnerable = |team: &str| {
+            SpriteContextBranch::Condition(Box::new(SpriteContextCondition::Invulnerable {
+                invulnerable: Some(SpriteContextBranch::Texture(ResourceId::gg2(format!(
+                    "{team}/uber"
+                )))),
+                vulnerable: Some(SpriteContextBranch::Texture(ResourceId::gg2(format!(
+                    "{team}/test"
+                )))),
+            }))
+        };
+
+        let sprite = SpriteContextAsset {
+            branch: SpriteContextBranch::Condition(Box::new(SpriteContextCondition::Team {
+                red: Some(invulnerable("red")),
+                blu: Some(invulnerable("blu")),
+                spectator: None,
+            })),
+        };
+
+        let expected = toml! {
+            [branch]
+            type = "team"
+
+            [branch.red]
+            type = "invulnerable"
+            invulnerable = "gg2:red/uber"
+            vulnerable = "gg2:red/test"
+
+            [branch.blu]
+            type = "invulnerable"
+            invulnerable = "gg2:blu/uber"
+            vulnerable = "gg2:blu/test"
+        };
+
+        let sprite_raw = toml::to_string(&sprite).unwrap();
+        let expected_raw = toml::to_string(&expected).unwrap();
+
+        assert_eq!(sprite_raw, expected_raw);
+    }
 }
@@ -1,5 +1,8 @@
 use crate::prelude::*;
 
+/// How much more of the map a zoomed in sniper can see
+const SNIPER_ZOOM: f32 = 1.5;
+
 /// Where the game is rendered from
 #[derive(Debug)]
 pub struct Camera {
@@ -9,6 +12,8 @@ pub struct Camera {
     pub clipping_near: f32,
     /// The far clipping plane's z
     pub clipping_far: f32,
+    /// Scales the size of the view
+    pub zoom: f32,
 }
 
 impl Default for Camera {
@@ -17,6 +22,7 @@ impl Default for Camera {
             translation: Vec2::ZERO,
             clipping_near: -100.0,
             clipping_far: 100.0,
+            zoom: 1.0,
         }
     }
 }
@@ -24,7 +30,13 @@ impl Default for Camera {
 impl ClientGame {
     pub async fn update_camera(&self) -> Result<(), ClientError> {
         if let Ok(player) = self.world.client_players().read().await.get_client() {
-            self.world.camera().write().await.translation = player.transform.translation.xy();
+            let mut camera = self.world.camera().write().await;
+
+            camera.translation = player.transform.translation.xy();
+            camera.zoom = match player.class_state {
+                ClassState::Sniper { zoomed: true, .. } => SNIPER_ZOOM,
+                _ => 1.0,
+            };
         }
 
         Ok(())
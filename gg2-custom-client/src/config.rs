@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     ops::{Deref, DerefMut},
     path::PathBuf,
 };
@@ -38,6 +39,7 @@ impl DerefMut for ClientConfig {
 pub struct ClientConfigRoot {
     pub networking: ClientConfigNetworking,
     pub game: ClientConfigGame,
+    pub settings: ClientConfigSettings,
     pub controls: ClientConfigControls,
     pub assets: ClientConfigAssets,
     pub debug: ClientConfigDebug,
@@ -47,10 +49,30 @@ pub struct ClientConfigRoot {
     _extra: toml::Table,
 }
 
+#[skip_serializing_none]
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ClientConfigGame {
     pub player_name: GGStringShort,
+    /// The name rewards are claimed under
+    pub reward_name: Option<GGStringShort>,
+    /// The secret rewards are claimed with, in hex
+    pub reward_key: Option<String>,
+}
+
+impl ClientConfigGame {
+    /// The reward name and decoded key, if both are set
+    pub fn reward_credentials(&self) -> Option<(GGStringShort, Vec<u8>)> {
+        let reward_name = self.reward_name.clone()?;
+
+        match hex::decode(self.reward_key.as_ref()?) {
+            Ok(reward_key) => Some((reward_name, reward_key)),
+            Err(error) => {
+                warn!("Reward key isn't valid hex: {error}");
+                None
+            }
+        }
+    }
 }
 
 impl Default for ClientConfigGame {
@@ -60,6 +82,24 @@ impl Default for ClientConfigGame {
                 .to_string()
                 .try_into()
                 .expect("Failed to create default player name"),
+            reward_name: None,
+            reward_key: None,
+        }
+    }
+}
+
+/// Preferences shared with the server
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClientConfigSettings {
+    /// Skip the respawn queue when the server allows it
+    pub queue_jump: bool,
+}
+
+impl From<&ClientConfigSettings> for ClientClientSettings {
+    fn from(settings: &ClientConfigSettings) -> Self {
+        Self {
+            queue_jump: settings.queue_jump,
         }
     }
 }
@@ -74,12 +114,21 @@ pub struct ClientConfigDebug {
 #[serde(default)]
 pub struct ClientConfigNetworking {
     pub default_server_address: String,
+    /// Where the server browser gets its list from
+    pub lobby_address: String,
+    /// Passwords sent to servers that ask for one, by server address
+    pub server_passwords: BTreeMap<String, GGStringShort>,
+    /// How many times to try getting back to a server after losing the connection
+    pub reconnect_attempts: u32,
 }
 
 impl Default for ClientConfigNetworking {
     fn default() -> Self {
         Self {
             default_server_address: format!("127.0.0.1:{}", gg2_common::networking::DEFAULT_PORT),
+            lobby_address: gg2_client::networking::lobby::DEFAULT_LOBBY_ADDRESS.to_string(),
+            server_passwords: BTreeMap::new(),
+            reconnect_attempts: 5,
         }
     }
 }
@@ -21,4 +21,6 @@ pub enum ClientError {
     ChannelGameRender(#[from] tokio::sync::mpsc::error::SendError<RenderMessage>),
     #[error("Network Error: {0}")]
     Network(#[from] NetworkError),
+    #[error("Plugin Error: {0}")]
+    Plugin(#[from] PluginError),
 }
@@ -195,6 +195,7 @@ impl ClientGame {
                 ClientGameMessage::SendClientMessage(client_message) => {
                     self.send_client_message(client_message).await?
                 }
+                ClientGameMessage::JoinServer(address) => self.join_server(&address).await,
             }
         }
 
@@ -205,6 +206,12 @@ impl ClientGame {
     pub async fn server_message(&self, message: ServerMessageGeneric) -> Result<(), ClientError> {
         match message {
             ServerMessageGeneric::ChangeMap(message) => self.event_map_change(message).await?,
+            ServerMessageGeneric::Balance(message) => {
+                self.event_balance(&message).await;
+                self.game
+                    .server_message(ServerMessageGeneric::Balance(message))
+                    .await?;
+            }
             _ => self.game.server_message(message).await?,
         }
 
@@ -1,19 +1,95 @@
+use std::time::Duration;
+
+use gg2_common::player::team::TeamSpawnable;
+use poll_promise::Promise;
+
 use crate::prelude::*;
 
 pub trait ClientGamemodeState: GamemodeState {
     fn deserialize(
         &mut self,
-        payload: &mut dyn Iterator<Item = u8>,
+        payload: &mut MessageReader,
         kind: PacketKind,
     ) -> Result<(), CommonError>;
 
     fn render_hud(&self, ctx: &egui::Context, world: &'static ClientWorld);
 }
 
+/// The state a map's gamemode starts in
+pub fn gamemode_state(map_data: &MapData) -> Box<dyn ClientGamemodeState> {
+    let control_points = ControlPointState::from_map(map_data);
+
+    match map_data.gamemode {
+        Gamemode::Arena => Box::new(ArenaState {
+            control_point: control_points.into_iter().next().unwrap_or_default(),
+            ..Default::default()
+        }),
+        Gamemode::ControlPoint | Gamemode::AttackDefenceControlPoint => {
+            Box::new(ControlPointsState::new(control_points))
+        }
+        Gamemode::KingOfTheHill | Gamemode::DualKingOfTheHill => {
+            Box::new(KingOfTheHillState::new(control_points))
+        }
+        Gamemode::Generator => Box::new(GeneratorState::default()),
+        // TODO: Implement the remaining gamemodes
+        _ => Box::new(CaptureTheFlagState::default()),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let seconds_left = duration.as_secs();
+    let minutes = seconds_left / 60;
+    let seconds = seconds_left % 60;
+    format!("{minutes:02}:{seconds:02}")
+}
+
+fn format_team(team: Option<TeamSpawnable>) -> &'static str {
+    match team {
+        Some(TeamSpawnable::Red) => "RED",
+        Some(TeamSpawnable::Blu) => "BLU",
+        None => "NEUTRAL",
+    }
+}
+
+/// Reads the state of each point, locking them all while `lock_timer` is running
+fn deserialize_control_points(
+    control_points: &mut [ControlPointState],
+    payload: &mut MessageReader,
+    lock_timer: Duration,
+) -> Result<(), CommonError> {
+    for control_point in control_points {
+        control_point.apply(RawControlPoint::deserialize(payload)?);
+
+        if !lock_timer.is_zero() {
+            control_point.locked = true;
+        }
+    }
+
+    Ok(())
+}
+
+fn render_control_points(ui: &mut egui::Ui, control_points: &[ControlPointState]) {
+    for (index, control_point) in control_points.iter().enumerate() {
+        let mut label = format!("CP{}: {}", index + 1, format_team(control_point.owner));
+
+        if control_point.locked {
+            label.push_str(" (locked)");
+        } else if let Some(team) = control_point.capturing_team {
+            label.push_str(&format!(
+                " ({} capturing: {})",
+                format_team(Some(team)),
+                control_point.capturing
+            ));
+        }
+
+        ui.label(label);
+    }
+}
+
 impl ClientGamemodeState for CaptureTheFlagState {
     fn deserialize(
         &mut self,
-        payload: &mut dyn Iterator<Item = u8>,
+        payload: &mut MessageReader,
         _kind: PacketKind,
     ) -> Result<(), CommonError> {
         self.match_timer = HudMatchTimer::deserialize(payload)?;
@@ -23,19 +99,195 @@ impl ClientGamemodeState for CaptureTheFlagState {
     }
 
     fn render_hud(&self, ctx: &egui::Context, world: &'static ClientWorld) {
+        let captures = Promise::spawn_async(world.round_state().read())
+            .block_and_take()
+            .captures
+            .clone();
+
         egui::TopBottomPanel::top("gamemode_ctf_hud").show(ctx, |ui| {
             ui.horizontal_centered(|ui| {
-                let seconds_left = self.match_timer.left.as_secs();
-                let minutes = seconds_left / 60;
-                let seconds = seconds_left % 60;
-                ui.label(format!("CTF: {minutes:02}:{seconds:02.2}"));
+                ui.label(format!("CTF: {}", format_duration(self.match_timer.left)));
                 let percent = (self.match_timer.left.as_secs_f32()
                     / self.match_timer.total.as_secs_f32())
                     * 100.0;
                 ui.label(format!("{percent:03.2}%"));
 
-                ui.label("RED: UNIMPLEMENTED");
-                ui.label("BLU: UNIMPLEMENTED");
+                ui.label(format!("RED: {}", captures.red_captures));
+                ui.label(format!("BLU: {}", captures.blu_captures));
+            });
+        });
+    }
+}
+
+impl ClientGamemodeState for ArenaState {
+    fn deserialize(
+        &mut self,
+        payload: &mut MessageReader,
+        kind: PacketKind,
+    ) -> Result<(), CommonError> {
+        if kind == PacketKind::FullUpdate {
+            self.apply_full(GamemodeHudArenaFull::deserialize(payload)?)?;
+        }
+
+        self.match_timer = HudMatchTimer::deserialize(payload)?;
+        self.control_point_unlock = payload.read_duration_u16_sec()?;
+        // TODO: What does this byte do?
+        let _round_start = payload.read_u8()?;
+        deserialize_control_points(
+            std::slice::from_mut(&mut self.control_point),
+            payload,
+            self.control_point_unlock,
+        )?;
+        debug!("{self:#?}");
+
+        Ok(())
+    }
+
+    fn render_hud(&self, ctx: &egui::Context, _world: &'static ClientWorld) {
+        egui::TopBottomPanel::top("gamemode_arena_hud").show(ctx, |ui| {
+            ui.horizontal_centered(|ui| {
+                ui.label(format!("ARENA: {}", format_duration(self.match_timer.left)));
+                ui.label(format!("RED WINS: {}", self.red_wins));
+                ui.label(format!("BLU WINS: {}", self.blu_wins));
+
+                match self.phase {
+                    ArenaPhase::WaitingForPlayers => {
+                        ui.label("Waiting for players");
+                    }
+                    ArenaPhase::RoundActive if !self.control_point_unlock.is_zero() => {
+                        ui.label(format!(
+                            "Point unlocks in {}",
+                            format_duration(self.control_point_unlock)
+                        ));
+                    }
+                    ArenaPhase::RoundActive => {
+                        ui.label("Point unlocked");
+                    }
+                    ArenaPhase::RoundOver => {
+                        let result = match self.winners {
+                            Team::Red => "RED wins the round",
+                            Team::Blu => "BLU wins the round",
+                            Team::Spectator => "Stalemate",
+                        };
+                        ui.label(format!(
+                            "{result}, next round in {}s",
+                            self.end_count.as_secs_f32().ceil()
+                        ));
+                    }
+                }
+            });
+        });
+    }
+}
+
+impl ClientGamemodeState for ControlPointsState {
+    fn deserialize(
+        &mut self,
+        payload: &mut MessageReader,
+        _kind: PacketKind,
+    ) -> Result<(), CommonError> {
+        self.match_timer = HudMatchTimer::deserialize(payload)?;
+        self.setup_timer = payload.read_duration_u16_sec()?;
+        deserialize_control_points(&mut self.control_points, payload, self.setup_timer)?;
+        debug!("{self:#?}");
+
+        Ok(())
+    }
+
+    fn render_hud(&self, ctx: &egui::Context, _world: &'static ClientWorld) {
+        egui::TopBottomPanel::top("gamemode_cp_hud").show(ctx, |ui| {
+            ui.horizontal_centered(|ui| {
+                ui.label(format!("CP: {}", format_duration(self.match_timer.left)));
+
+                if !self.setup_timer.is_zero() {
+                    ui.label(format!("Setup: {}", format_duration(self.setup_timer)));
+                }
+
+                render_control_points(ui, &self.control_points);
+            });
+        });
+    }
+}
+
+impl ClientGamemodeState for KingOfTheHillState {
+    fn deserialize(
+        &mut self,
+        payload: &mut MessageReader,
+        _kind: PacketKind,
+    ) -> Result<(), CommonError> {
+        self.timer = HudKothTimer::deserialize(payload)?;
+        deserialize_control_points(&mut self.control_points, payload, self.timer.capture_unlock)?;
+        debug!("{self:#?}");
+
+        Ok(())
+    }
+
+    fn render_hud(&self, ctx: &egui::Context, _world: &'static ClientWorld) {
+        egui::TopBottomPanel::top("gamemode_koth_hud").show(ctx, |ui| {
+            ui.horizontal_centered(|ui| {
+                ui.label(format!("RED: {}", format_duration(self.timer.red_timer)));
+                ui.label(format!("BLU: {}", format_duration(self.timer.blu_timer)));
+
+                if !self.timer.capture_unlock.is_zero() {
+                    ui.label(format!(
+                        "Point unlocks in {}",
+                        format_duration(self.timer.capture_unlock)
+                    ));
+                }
+
+                render_control_points(ui, &self.control_points);
+            });
+        });
+    }
+}
+
+impl ClientGamemodeState for GeneratorState {
+    fn deserialize(
+        &mut self,
+        payload: &mut MessageReader,
+        _kind: PacketKind,
+    ) -> Result<(), CommonError> {
+        self.match_timer = HudMatchTimer::deserialize(payload)?;
+        self.blu_generator = RawGenerator::deserialize(payload)?;
+        self.red_generator = RawGenerator::deserialize(payload)?;
+        debug!("{self:#?}");
+
+        Ok(())
+    }
+
+    fn render_hud(&self, ctx: &egui::Context, _world: &'static ClientWorld) {
+        egui::TopBottomPanel::top("gamemode_generator_hud").show(ctx, |ui| {
+            ui.horizontal_centered(|ui| {
+                ui.label(format!("GEN: {}", format_duration(self.match_timer.left)));
+
+                for team in [TeamSpawnable::Red, TeamSpawnable::Blu] {
+                    let generator = self.generator(team);
+                    let health = f32::from(generator.health) / f32::from(GENERATOR_MAX_HEALTH);
+                    let shield =
+                        f32::from(generator.shield_health) / f32::from(GENERATOR_MAX_SHIELD_HEALTH);
+
+                    ui.label(format_team(Some(team)));
+                    ui.add(
+                        egui::ProgressBar::new(health)
+                            .desired_width(96.0)
+                            .text(format!("{}", generator.health)),
+                    );
+
+                    if generator.shield_health > 0 {
+                        ui.add(
+                            egui::ProgressBar::new(shield)
+                                .desired_width(48.0)
+                                .text(format!("{}", generator.shield_health)),
+                        );
+                    }
+                }
+
+                if let Some(winners) = self.winners() {
+                    ui.label(format!(
+                        "{} destroyed the generator",
+                        format_team(Some(winners))
+                    ));
+                }
             });
         });
     }
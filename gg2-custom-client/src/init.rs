@@ -9,6 +9,7 @@ const BUILTIN_ASSET_PACKS: [&str; 2] = ["builtin", "builtin-rs"];
 static WORLD: OnceLock<ClientWorld> = OnceLock::new();
 
 pub mod cli;
+mod probe;
 
 pub struct App {
     pub world: &'static ClientWorld,
@@ -91,6 +92,13 @@ impl App {
             .build()
             .expect("Failed building the Runtime");
 
+        if let Some(ClientCliSubcommand::Probe(arguments)) =
+            &self.world.client_cli_arguments().command
+        {
+            let answered = runtime.block_on(self.probe(arguments));
+            std::process::exit(i32::from(!answered));
+        }
+
         runtime.block_on(self.setup())?;
 
         runtime
@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use gg2_custom_common::init::cli::*;
 
 #[derive(Debug, Parser)]
@@ -11,6 +11,22 @@ pub struct ClientCliArguments {
 #[derive(Debug, Subcommand)]
 pub enum ClientCliSubcommand {
     JoinServer(CommonCliJoinServer),
+    Probe(ClientCliProbe),
+}
+
+/// Asks a server about itself without joining, then exits
+#[derive(Debug, Args)]
+pub struct ClientCliProbe {
+    /// The server to probe
+    /// Defaults to the client config's default server url
+    #[arg(long)]
+    pub server_url: Option<String>,
+    /// Prints the result as JSON
+    #[arg(long)]
+    pub json: bool,
+    /// Seconds to wait for the server to answer
+    #[arg(long, default_value_t = 5)]
+    pub timeout: u64,
 }
 
 pub fn init() -> ClientCliArguments {
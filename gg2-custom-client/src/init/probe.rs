@@ -0,0 +1,163 @@
+use std::{fmt::Display, time::Duration};
+
+use gg2_client::networking::{
+    connect::with_default_port,
+    probe::{ProbeReport, ProbeResponse, probe},
+};
+use serde::Serialize;
+
+use super::{App, cli::ClientCliProbe};
+use crate::prelude::*;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ProbeStatus {
+    Online,
+    PasswordRequired,
+    IncompatibleProtocol,
+    Unreachable,
+}
+
+/// What's printed about a probed server
+#[skip_serializing_none]
+#[derive(Debug, Serialize)]
+struct ProbeOutput {
+    address: String,
+    status: ProbeStatus,
+    round_trip_ms: Option<f64>,
+    server_name: Option<String>,
+    map_name: Option<String>,
+    /// Not present when the map is builtin
+    map_md5: Option<String>,
+    plugins_required: Option<bool>,
+    plugins: Option<Vec<String>>,
+    error: Option<String>,
+}
+
+impl ProbeOutput {
+    fn unreachable(address: String, error: &CommonError) -> Self {
+        Self {
+            address,
+            status: ProbeStatus::Unreachable,
+            round_trip_ms: None,
+            server_name: None,
+            map_name: None,
+            map_md5: None,
+            plugins_required: None,
+            plugins: None,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+impl From<ProbeReport> for ProbeOutput {
+    fn from(report: ProbeReport) -> Self {
+        let mut output = Self {
+            address: report.address,
+            status: ProbeStatus::Online,
+            round_trip_ms: Some(report.round_trip.as_secs_f64() * 1000.0),
+            server_name: None,
+            map_name: None,
+            map_md5: None,
+            plugins_required: None,
+            plugins: None,
+            error: None,
+        };
+
+        match report.response {
+            ProbeResponse::Hello(hello) => {
+                output.server_name = Some(hello.server_name.into());
+                output.map_name = Some(hello.map_name.into());
+                output.map_md5 = hello.map_md5.map(|md5| format!("{md5:032x}"));
+                output.plugins_required = Some(hello.plugins_required);
+                output.plugins = Some(hello.plugins.iter().map(ToString::to_string).collect());
+            }
+            ProbeResponse::PasswordRequired => output.status = ProbeStatus::PasswordRequired,
+            ProbeResponse::IncompatibleProtocol => {
+                output.status = ProbeStatus::IncompatibleProtocol;
+            }
+        }
+
+        output
+    }
+}
+
+impl Display for ProbeOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self.status {
+            ProbeStatus::Online => "online",
+            ProbeStatus::PasswordRequired => "online, password required",
+            ProbeStatus::IncompatibleProtocol => "online, incompatible protocol",
+            ProbeStatus::Unreachable => "unreachable",
+        };
+
+        write!(f, "{}: {status}", self.address)?;
+
+        if let Some(round_trip_ms) = self.round_trip_ms {
+            write!(f, " ({round_trip_ms:.0} ms)")?;
+        }
+
+        writeln!(f)?;
+
+        if let Some(server_name) = &self.server_name {
+            writeln!(f, "Name: {server_name}")?;
+        }
+
+        if let Some(map_name) = &self.map_name {
+            writeln!(f, "Map: {map_name}")?;
+        }
+
+        if let Some(plugins) = &self.plugins {
+            let plugins = if plugins.is_empty() {
+                "none".to_string()
+            } else {
+                plugins.join(", ")
+            };
+
+            if self.plugins_required == Some(true) {
+                writeln!(f, "Plugins (required): {plugins}")?;
+            } else {
+                writeln!(f, "Plugins: {plugins}")?;
+            }
+        }
+
+        if let Some(error) = &self.error {
+            writeln!(f, "Error: {error}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl App {
+    /// Prints what a server says about itself, returning whether it answered
+    pub(super) async fn probe(&self, arguments: &ClientCliProbe) -> bool {
+        let address = match &arguments.server_url {
+            Some(url) => url.clone(),
+            None => self
+                .world
+                .config()
+                .read()
+                .await
+                .networking
+                .default_server_address
+                .clone(),
+        };
+
+        let output = match probe(&address, Duration::from_secs(arguments.timeout)).await {
+            Ok(report) => ProbeOutput::from(report),
+            Err(error) => ProbeOutput::unreachable(with_default_port(&address), &error),
+        };
+
+        if arguments.json {
+            match serde_json::to_string(&output) {
+                Ok(json) => println!("{json}"),
+                Err(error) => error!("Failed to serialize probe: {error}"),
+            }
+        } else {
+            print!("{output}");
+        }
+
+        !matches!(output.status, ProbeStatus::Unreachable)
+    }
+}
@@ -7,7 +7,9 @@ mod init;
 mod input;
 mod map;
 mod networking;
+mod notice;
 mod player;
+mod plugin;
 mod prelude;
 mod render;
 mod sync;
@@ -1,11 +1,36 @@
 use crate::prelude::*;
 
+pub mod cache;
+
 #[derive(Debug, Default)]
 pub struct MapInfo {
     pub current_map: Option<(ResourceId, MapData)>,
 }
 
 impl ClientGame {
+    /// Whether the map can be loaded without downloading it
+    pub async fn map_available(
+        &self,
+        map_name: &GGStringShort,
+        map_md5: Option<u128>,
+    ) -> Result<bool, ClientError> {
+        // Builtin maps can't be downloaded
+        if map_md5.is_none() {
+            return Ok(true);
+        }
+
+        let map_id = ResourceId::gg2((**map_name).clone());
+        let map_buffer = self
+            .world
+            .asset_server()
+            .read()
+            .await
+            .find_map(&map_id, map_md5, self.world.map_cache())
+            .await?;
+
+        Ok(map_buffer.is_some())
+    }
+
     pub async fn event_map_change(&self, message: ServerChangeMap) -> Result<(), ClientError> {
         let map_id = ResourceId::gg2((*message.map_name).clone());
         info!("Map loading: {map_id}");
@@ -15,10 +40,13 @@ impl ClientGame {
             .asset_server()
             .read()
             .await
-            .load_map(&map_id)
+            .load_map(&map_id, message.map_md5, self.world.map_cache())
             .await?;
 
+        *self.world.client_gamemode_state().write().await = Some(gamemode_state(&data));
+        *self.world.moving_platforms().write().await = MovingPlatform::from_map(&data);
         self.world.map_info().write().await.current_map = Some((map_id.clone(), data));
+        *self.world.round_state().write().await = RoundState::default();
 
         self.world
             .render_channel()
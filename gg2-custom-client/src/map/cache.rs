@@ -0,0 +1,224 @@
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use tokio::{fs::File, io::AsyncWriteExt};
+
+use crate::prelude::*;
+
+const CACHE_DIRECTORY: &str = "gang-garrison-rs";
+const MAPS_DIRECTORY: &str = "maps";
+const PART_EXTENSION: &str = "part";
+/// The largest map the client will download, well above any real map
+const MAX_MAP_LENGTH: u32 = 4 * 1024 * 1024;
+
+/// Downloaded maps stored by their MD5 hash
+#[derive(Debug, Clone)]
+pub struct MapCache {
+    directory: PathBuf,
+}
+
+impl MapCache {
+    #[inline]
+    #[must_use]
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// The user's cache directory, or next to the executable when there isn't one
+    pub fn user(executable_directory: &Path) -> Self {
+        let directory = dirs::cache_dir()
+            .map(|directory| directory.join(CACHE_DIRECTORY))
+            .unwrap_or_else(|| executable_directory.to_path_buf())
+            .join(MAPS_DIRECTORY);
+
+        Self::new(directory)
+    }
+
+    fn path(&self, md5: u128) -> PathBuf {
+        self.directory
+            .join(format!("{md5:032x}"))
+            .with_extension(AssetType::Map.extension())
+    }
+
+    /// Reads a downloaded map, ignoring it if it no longer matches its hash
+    pub async fn load(&self, md5: u128) -> Result<Option<Vec<u8>>, AssetError> {
+        let path = self.path(md5);
+
+        let map_buffer = match tokio::fs::read(&path).await {
+            Ok(map_buffer) => map_buffer,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(error.into()),
+        };
+
+        if map_md5(&map_buffer) == md5 {
+            Ok(Some(map_buffer))
+        } else {
+            warn!("Cached map doesn't match its hash: {}", path.display());
+            Ok(None)
+        }
+    }
+
+    /// Creates the file a map will be downloaded into
+    pub async fn begin_download(&self, md5: u128) -> Result<MapDownload, AssetError> {
+        tokio::fs::create_dir_all(&self.directory).await?;
+
+        let path = self.path(md5);
+        let part_path = path.with_extension(PART_EXTENSION);
+        let file = File::create(&part_path).await?;
+
+        Ok(MapDownload {
+            md5,
+            remaining: None,
+            file,
+            part_path,
+            path,
+        })
+    }
+}
+
+/// A map being streamed from the server into the cache
+#[derive(Debug)]
+pub struct MapDownload {
+    md5: u128,
+    /// Unknown until the server starts sending
+    remaining: Option<usize>,
+    file: File,
+    /// Where the map is written until it's verified
+    part_path: PathBuf,
+    path: PathBuf,
+}
+
+impl MapDownload {
+    /// Refuses maps larger than [`MAX_MAP_LENGTH`]
+    pub fn start(&mut self, length: u32) -> Result<(), AssetError> {
+        if length > MAX_MAP_LENGTH {
+            return Err(AssetError::MapTooLarge(length));
+        }
+
+        self.remaining = Some(length as usize);
+
+        Ok(())
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_started(&self) -> bool {
+        self.remaining.is_some()
+    }
+
+    /// How many more bytes are expected
+    #[inline]
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.remaining.unwrap_or_default()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.remaining == Some(0)
+    }
+
+    pub async fn write(&mut self, buf: &[u8]) -> Result<(), AssetError> {
+        self.file.write_all(buf).await?;
+
+        if let Some(remaining) = &mut self.remaining {
+            *remaining = remaining.saturating_sub(buf.len());
+        }
+
+        Ok(())
+    }
+
+    /// Moves the map into the cache if it matches the server's hash
+    pub async fn finish(mut self) -> Result<(), AssetError> {
+        self.file.flush().await?;
+        drop(self.file);
+
+        let found = map_md5(&tokio::fs::read(&self.part_path).await?);
+
+        if found != self.md5 {
+            tokio::fs::remove_file(&self.part_path).await?;
+
+            return Err(AssetError::MapHash {
+                expected: self.md5,
+                found,
+            });
+        }
+
+        tokio::fs::rename(&self.part_path, &self.path).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP_FILE: &[u8] = b"Not really a PNG";
+
+    fn test_cache(name: &str) -> MapCache {
+        let directory = std::env::temp_dir()
+            .join(format!("{CACHE_DIRECTORY}-test"))
+            .join(name);
+        let _ = std::fs::remove_dir_all(&directory);
+
+        MapCache::new(directory)
+    }
+
+    async fn download(cache: &MapCache, md5: u128, file: &[u8]) -> Result<(), AssetError> {
+        let mut download = cache.begin_download(md5).await?;
+        download.start(file.len() as u32)?;
+
+        for chunk in file.chunks(5) {
+            assert!(!download.is_finished());
+            download.write(chunk).await?;
+        }
+
+        assert!(download.is_finished());
+        download.finish().await
+    }
+
+    #[tokio::test]
+    async fn download_and_load() {
+        let cache = test_cache("download_and_load");
+        let md5 = map_md5(MAP_FILE);
+
+        assert_eq!(cache.load(md5).await.unwrap(), None);
+        download(&cache, md5, MAP_FILE).await.unwrap();
+        assert_eq!(cache.load(md5).await.unwrap().as_deref(), Some(MAP_FILE));
+    }
+
+    #[tokio::test]
+    async fn reject_mismatched_hash() {
+        let cache = test_cache("reject_mismatched_hash");
+        let md5 = map_md5(b"Some other map");
+
+        assert!(matches!(
+            download(&cache, md5, MAP_FILE).await,
+            Err(AssetError::MapHash { .. })
+        ));
+        assert_eq!(cache.load(md5).await.unwrap(), None);
+
+        // A copy changed on disk is ignored
+        tokio::fs::write(cache.path(md5), MAP_FILE).await.unwrap();
+        assert_eq!(cache.load(md5).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn reject_oversized_map() {
+        let cache = test_cache("reject_oversized_map");
+        let mut download = cache.begin_download(map_md5(MAP_FILE)).await.unwrap();
+
+        assert!(matches!(
+            download.start(MAX_MAP_LENGTH + 1),
+            Err(AssetError::MapTooLarge(length)) if length == MAX_MAP_LENGTH + 1
+        ));
+        assert!(!download.is_started());
+
+        download.start(MAX_MAP_LENGTH).unwrap();
+        assert_eq!(download.remaining(), MAX_MAP_LENGTH as usize);
+    }
+}
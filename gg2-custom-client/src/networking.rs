@@ -1,2 +1,6 @@
 pub mod io;
+pub mod latency;
+pub mod password;
+pub mod reconnect;
+pub mod reward;
 pub mod update;
@@ -1,12 +1,10 @@
-use std::{
-    collections::VecDeque,
-    net::ToSocketAddrs,
-    ops::{Deref, DerefMut},
-    sync::Arc,
-};
+use std::{collections::VecDeque, net::IpAddr, sync::Arc, time::Duration};
 
 use gg2_client::networking::{
-    message::{ClientNetworkDeserializationContext, ClientNetworkSerialize},
+    connect::{open_stream, with_default_port},
+    message::{
+        ClientNetworkDeserializationContext, ClientNetworkDeserialize, ClientNetworkSerialize,
+    },
     state::NetworkingState,
 };
 use tokio::{
@@ -16,15 +14,22 @@ use tokio::{
         tcp::{OwnedReadHalf, OwnedWriteHalf},
     },
     sync::{
-        Mutex,
+        Mutex, RwLockWriteGuard,
         mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+        watch,
     },
     task::JoinHandle,
+    time::Instant,
 };
 
+use super::reward::reward_response;
 use crate::prelude::*;
 
 pub const MAX_PACKET_LENGTH: usize = 1024;
+/// How long resolving and connecting to a server may take
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long the server may go without sending anything
+const RECEIVE_TIMEOUT: Duration = Duration::from_secs(15);
 
 #[derive(Debug)]
 pub struct SyncChannel<T> {
@@ -43,7 +48,9 @@ impl<T> Default for SyncChannel<T> {
 #[derive(Debug)]
 pub enum ClientNetworkEvent {
     Connected,
-    Disconnected,
+    /// The server couldn't be resolved or connected to
+    ConnectFailed(NetworkError),
+    Disconnected(DisconnectReason),
     Error(NetworkError),
 }
 
@@ -52,6 +59,9 @@ pub struct ServerConnection {
     receive_task: JoinHandle<()>,
     send_task: JoinHandle<()>,
     send_message: UnboundedSender<Vec<u8>>,
+    server_ip: Option<IpAddr>,
+    /// When the server last sent anything
+    last_received: watch::Receiver<Instant>,
 }
 
 impl ServerConnection {
@@ -64,58 +74,156 @@ impl ServerConnection {
 #[derive(Debug, Default)]
 pub struct NetworkClient {
     server_connection: Option<ServerConnection>,
-    receive_message: Arc<Mutex<VecDequeIter<u8>>>,
+    /// Present while resolving and connecting to a server
+    connect_task: Option<JoinHandle<()>>,
+    receive_message: Arc<Mutex<VecDeque<u8>>>,
     pub network_events: SyncChannel<ClientNetworkEvent>,
     connection_events: SyncChannel<TcpStream>,
     pub connection_state: NetworkingState,
+    /// The address last connected to
+    server_address: Option<String>,
+    /// Present while in [`NetworkingState::DownloadingMap`]
+    pub map_download: Option<MapDownload>,
+    pub latency: Latency,
+    /// Present while the server waits on a password from the player
+    pub password_prompt: Option<PasswordPrompt>,
+    /// Present until the server answers the password
+    pub sent_password: Option<SentPassword>,
+    /// The last connection ended with a wrong password
+    pub password_rejected: bool,
+    pub reconnect: Reconnect,
 }
 
 impl NetworkClient {
-    // Connects to a new server
-    pub async fn connect(&mut self, url: &str) -> Result<(), NetworkError> {
+    /// Starts connecting to a new server
+    ///
+    /// The outcome arrives as a [`ClientNetworkEvent`]
+    pub fn connect(&mut self, url: &str) {
         info!("Connecting to server: {url}");
 
         if self.server_connection.is_some() {
-            self.disconnect();
+            self.disconnect(DisconnectReason::Left);
         }
 
-        let connection_event_sender = self.connection_events.sender.clone();
+        if let Some(connect_task) = self.connect_task.take() {
+            connect_task.abort();
+        }
 
-        let url = if url.contains(':') {
-            url
-        } else {
-            &*format!("{}:{}", url, gg2_common::networking::DEFAULT_PORT)
-        };
+        // Anything left over belongs to the old connection
+        self.receive_message = Arc::default();
+        self.network_events = SyncChannel::default();
+        self.connection_events = SyncChannel::default();
+        self.map_download = None;
+        self.password_prompt = None;
+        self.sent_password = None;
 
-        let address = &*(url)
-            .to_socket_addrs()
-            .expect("Failed to parse server address")
-            .collect::<Vec<_>>();
+        let url = with_default_port(url);
 
-        let stream = match TcpStream::connect(address).await {
-            Ok(stream) => stream,
-            Err(error) => {
-                return Err(NetworkError::Connection(error, url.to_string()));
-            }
-        };
+        self.server_address = Some(url.clone());
+        self.latency = Latency::default();
 
-        connection_event_sender
-            .send(stream)
-            .map_err(|_| NetworkError::ConnectSend)
+        self.connect_task = Some(tokio::spawn(connect_task(
+            url,
+            self.connection_events.sender.clone(),
+            self.network_events.sender.clone(),
+        )));
     }
 
-    pub fn disconnect(&mut self) {
+    pub fn disconnect(&mut self, reason: DisconnectReason) {
         if let Some(connection) = self.server_connection.take() {
-            self.connection_state = NetworkingState::Disconnected;
+            info!("Disconnected: {reason}");
+
+            self.connection_state = NetworkingState::Disconnected(Some(reason));
+            self.password_prompt = None;
+            self.sent_password = None;
+            self.password_rejected = reason == DisconnectReason::PasswordWrong;
             connection.stop();
 
             let _ = self
                 .network_events
                 .sender
-                .send(ClientNetworkEvent::Disconnected);
+                .send(ClientNetworkEvent::Disconnected(reason));
+        }
+    }
+
+    /// Reconnects to the same server, such as to download a new map
+    pub fn rejoin(&mut self) -> Result<(), NetworkError> {
+        let url = self
+            .server_address
+            .clone()
+            .ok_or(NetworkError::NotConnected)?;
+
+        if let Some(connection) = self.server_connection.take() {
+            connection.stop();
+        }
+
+        self.connect(&url);
+        self.connection_state = NetworkingState::AttemptingConnection;
+
+        Ok(())
+    }
+
+    /// The address last connected to
+    #[inline]
+    pub fn server_address(&self) -> Option<&str> {
+        self.server_address.as_deref()
+    }
+
+    pub async fn send_password(
+        &mut self,
+        password: GGStringShort,
+        remember: bool,
+    ) -> Result<(), CommonError> {
+        self.send(ClientPassword {
+            password: password.clone(),
+        })
+        .await?;
+        self.sent_password = Some(SentPassword { password, remember });
+
+        Ok(())
+    }
+
+    /// Drops the connection once the server stops sending anything
+    pub fn check_timeout(&mut self, now: Instant) {
+        // The server is waiting on the player
+        if self.password_prompt.is_some() {
+            return;
+        }
+
+        let timed_out = self.server_connection.as_ref().is_some_and(|connection| {
+            now.saturating_duration_since(*connection.last_received.borrow()) >= RECEIVE_TIMEOUT
+        });
+
+        if timed_out {
+            self.disconnect(DisconnectReason::TimedOut);
         }
     }
 
+    /// Moves received map bytes to disk, returning whether the download finished
+    pub async fn download_map_bytes(&mut self) -> Result<bool, AssetError> {
+        let Some(download) = self.map_download.as_mut() else {
+            return Ok(false);
+        };
+
+        let buffer = {
+            let queue = &mut *self.receive_message.lock().await;
+            let length = queue.len().min(download.remaining());
+            queue.drain(..length).collect::<Vec<_>>()
+        };
+
+        download.write(&buffer).await?;
+
+        if !download.is_finished() {
+            return Ok(false);
+        }
+
+        if let Some(download) = self.map_download.take() {
+            download.finish().await?;
+        }
+
+        Ok(true)
+    }
+
     fn send_raw(&self, buffer: Vec<u8>) -> Result<(), NetworkError> {
         trace!("Sending message to server.");
         self.server_connection
@@ -144,11 +252,30 @@ impl NetworkClient {
         Ok(self.send_raw(buffer)?)
     }
 
+    /// Proves to the server that the client holds its reward key
+    pub async fn answer_reward_challenge(
+        &self,
+        reward_key: &[u8],
+        challenge: &[u8],
+    ) -> Result<(), CommonError> {
+        let server_ip = self
+            .server_connection
+            .as_ref()
+            .and_then(|connection| connection.server_ip)
+            .ok_or(NetworkError::NotConnected)?;
+
+        let response = reward_response(reward_key, challenge, server_ip);
+        self.send_message(ClientRewardChallengeResponse { response })
+            .await
+    }
+
     /// Sets up send and receive threads when connecting
     pub fn handle_connection_event(&mut self) {
         if let Ok(connection) = self.connection_events.receiver.try_recv() {
+            let server_ip = connection.peer_addr().ok().map(|address| address.ip());
             let (read_socket, send_socket) = connection.into_split();
             let (send_message, receive_message) = unbounded_channel();
+            let (last_received_sender, last_received) = watch::channel(Instant::now());
 
             self.server_connection = Some(ServerConnection {
                 send_task: tokio::spawn(send_task(
@@ -159,10 +286,14 @@ impl NetworkClient {
                 receive_task: tokio::spawn(receive_task(
                     read_socket,
                     Arc::clone(&self.receive_message),
+                    last_received_sender,
                     self.network_events.sender.clone(),
                 )),
                 send_message,
+                server_ip,
+                last_received,
             });
+            self.connect_task = None;
 
             let _ = self
                 .network_events
@@ -171,44 +302,73 @@ impl NetworkClient {
         };
     }
 
+    /// Decodes the next message once it has fully arrived
     pub async fn pop_message(
         &self,
-        context: &impl ClientNetworkDeserializationContext,
+        world: &ClientWorld,
     ) -> Result<Option<ServerMessageGeneric>, CommonError> {
-        let generic_message = {
-            let queue = &mut *self.receive_message.lock().await;
+        let queue = &mut *self.receive_message.lock().await;
 
-            if queue.is_empty() {
-                return Ok(None);
-            }
+        if queue.is_empty() {
+            return Ok(None);
+        }
 
-            // Forces queue to be dropped preventing dead lock on error
-            use gg2_client::networking::message::ClientNetworkDeserialize;
-            ServerMessageGeneric::deserialize(queue, context).await
-        };
+        let mut context = ClientDeserializationContext::new(world).await;
+        decode_message(queue, &mut context)
+    }
+}
 
-        match generic_message {
-            Ok(message) => Ok(Some(message)),
-            Err(error) => {
-                // Dead lock would happen here
-                self.purge_queue().await;
-                Err(error)
-            }
+/// Decodes a message from the front of the queue,
+/// leaving it in place until all of it has arrived
+fn decode_message(
+    queue: &mut VecDeque<u8>,
+    context: &mut impl ClientNetworkDeserializationContext,
+) -> Result<Option<ServerMessageGeneric>, CommonError> {
+    let mut payload = MessageReader::new(queue.make_contiguous());
+
+    match ServerMessageGeneric::deserialize(&mut payload, context) {
+        Ok(message) => {
+            let message_length = payload.consumed();
+            queue.drain(..message_length);
+            Ok(Some(message))
+        }
+        Err(CommonError::Network(NetworkError::UnexpectedEOF)) => {
+            trace!("Waiting for the rest of the message...");
+            Ok(None)
+        }
+        Err(error) => {
+            purge_queue(queue);
+            Err(error)
         }
     }
+}
 
-    /// Clears the message queue in the event something goes wrong
-    pub async fn purge_queue(&self) {
-        let mut queue = self.receive_message.lock().await;
+/// Clears the message queue in the event something goes wrong
+fn purge_queue(queue: &mut VecDeque<u8>) {
+    if !queue.is_empty() {
+        debug!("Purging queue...");
 
-        if !queue.is_empty() {
-            debug!("Purging queue...");
+        let data = Vec::from(std::mem::take(queue));
+        debug!("'{}'", data.escape_ascii());
+    }
+}
 
-            let mut old_queue = VecDequeIter::default();
+/// Resolves and connects to a server without holding up the game
+async fn connect_task(
+    url: String,
+    connection_event_sender: UnboundedSender<TcpStream>,
+    network_event_sender: UnboundedSender<ClientNetworkEvent>,
+) {
+    let result = tokio::time::timeout(CONNECT_TIMEOUT, open_stream(&url))
+        .await
+        .unwrap_or_else(|_| Err(NetworkError::ConnectTimeout(url.clone())));
 
-            std::mem::swap(&mut *queue, &mut old_queue);
-            let data = old_queue.collect::<Vec<_>>();
-            debug!("'{}'", data.escape_ascii());
+    match result {
+        Ok(stream) => {
+            let _ = connection_event_sender.send(stream);
+        }
+        Err(error) => {
+            let _ = network_event_sender.send(ClientNetworkEvent::ConnectFailed(error));
         }
     }
 }
@@ -229,22 +389,22 @@ async fn send_task(
         trace!("Succesfully written all!");
     }
 
-    let _ = network_event_sender.send(ClientNetworkEvent::Disconnected);
+    let _ = network_event_sender.send(ClientNetworkEvent::Disconnected(
+        DisconnectReason::ConnectionClosed,
+    ));
 }
 
 // Receives data from server and passes network packets
 async fn receive_task(
     mut read_socket: OwnedReadHalf,
-    receive_messages: Arc<Mutex<VecDequeIter<u8>>>,
+    receive_messages: Arc<Mutex<VecDeque<u8>>>,
+    last_received: watch::Sender<Instant>,
     network_event_sender: UnboundedSender<ClientNetworkEvent>,
 ) {
     let mut buffer = [0; MAX_PACKET_LENGTH];
 
-    while let Ok(length) = read_socket.read(&mut buffer).await {
-        if length == 0 {
-            continue;
-        }
-
+    // Zero bytes means the server closed the connection
+    while let Ok(length @ 1..) = read_socket.read(&mut buffer).await {
         trace!(
             "Received {} bytes: {}",
             length,
@@ -252,52 +412,53 @@ async fn receive_task(
         );
 
         receive_messages.lock().await.extend(&buffer[..length]);
+        last_received.send_replace(Instant::now());
     }
 
-    let _ = network_event_sender.send(ClientNetworkEvent::Disconnected);
+    let _ = network_event_sender.send(ClientNetworkEvent::Disconnected(
+        DisconnectReason::ConnectionClosed,
+    ));
 }
 
-#[derive(Debug, Default)]
-struct VecDequeIter<T>(VecDeque<T>);
-
-impl<T> Iterator for VecDequeIter<T> {
-    type Item = T;
-
-    fn next(&mut self) -> Option<T> {
-        self.0.pop_front()
-    }
+/// What decoding needs from the world, locked for the length of a message
+struct ClientDeserializationContext<'a> {
+    players_length: u8,
+    gamemode_state: RwLockWriteGuard<'a, Option<Box<dyn ClientGamemodeState>>>,
+    control_points_length: Option<u8>,
+    moving_platforms_length: Option<u8>,
 }
 
-impl<T> Deref for VecDequeIter<T> {
-    type Target = VecDeque<T>;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-impl<T> DerefMut for VecDequeIter<T> {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+impl<'a> ClientDeserializationContext<'a> {
+    async fn new(world: &'a ClientWorld) -> Self {
+        let players_length = world.players().read().await.len();
+        let map_info = world.map_info().read().await;
+        let current_map = map_info.current_map.as_ref();
+        let control_points_length =
+            current_map.map(|(_, map_data)| map_data.control_points_length());
+        let moving_platforms_length =
+            current_map.map(|(_, map_data)| map_data.moving_platforms_length());
+        drop(map_info);
+
+        Self {
+            players_length,
+            gamemode_state: world.client_gamemode_state().write().await,
+            control_points_length,
+            moving_platforms_length,
+        }
     }
 }
 
-impl ClientNetworkDeserializationContext for ClientWorld {
-    async fn players_length(&self) -> u8 {
-        self.players().read().await.len()
+impl ClientNetworkDeserializationContext for ClientDeserializationContext<'_> {
+    fn players_length(&self) -> u8 {
+        self.players_length
     }
 
-    async fn deserialize_gamemode_state<I>(
-        &self,
-        payload: &mut I,
+    fn deserialize_gamemode_state(
+        &mut self,
+        payload: &mut MessageReader,
         kind: PacketKind,
-    ) -> Result<(), CommonError>
-    where
-        I: Iterator<Item = u8>,
-    {
-        let mut gamemode = self.client_gamemode_state().write().await;
-
-        if let Some(state) = gamemode.as_mut() {
+    ) -> Result<(), CommonError> {
+        if let Some(state) = self.gamemode_state.as_mut() {
             state.deserialize(payload, kind)?;
         } else {
             error!("Gamemode unknown for deserialization.");
@@ -306,33 +467,280 @@ impl ClientNetworkDeserializationContext for ClientWorld {
         Ok(())
     }
 
-    async fn current_map_control_points_length(&self) -> Result<u8, CommonError> {
-        self.map_info()
-            .read()
-            .await
-            .current_map
-            .as_ref()
-            .ok_or(CommonError::MapUnloaded)
-            .map(|(_, map_data)| map_data.control_points_length)
+    fn current_map_control_points_length(&self) -> Result<u8, CommonError> {
+        self.control_points_length.ok_or(CommonError::MapUnloaded)
+    }
+
+    fn current_map_moving_platforms_length(&self) -> Result<u8, CommonError> {
+        self.moving_platforms_length.ok_or(CommonError::MapUnloaded)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{net::Ipv4Addr, time::Duration};
+
+    use gg2_client::networking::message::server::ClientNetworkDeserializeHud;
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    /// A full update captured from `gg2-custom-server` hosting ctf_2dfort
+    /// Sent to the second player to join, while the first is alive on red
+    // TODO: Replace with a capture from an original GG2 server, since this only
+    // shows the client reads what our own server writes
+    const FULL_UPDATE: &[u8] = &[
+        8, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 218, 7, 118, 17,
+        0, 0, 100, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 3, 0, 0, 5, 15, 129, 77, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+        255,
+    ];
+
+    struct TestContext;
+
+    impl ClientNetworkDeserializationContext for TestContext {
+        fn players_length(&self) -> u8 {
+            0
+        }
+
+        fn deserialize_gamemode_state(
+            &mut self,
+            payload: &mut MessageReader,
+            _kind: PacketKind,
+        ) -> Result<(), CommonError> {
+            HudMatchTimer::deserialize(payload).map(|_| ())
+        }
+
+        fn current_map_control_points_length(&self) -> Result<u8, CommonError> {
+            Ok(0)
+        }
+
+        fn current_map_moving_platforms_length(&self) -> Result<u8, CommonError> {
+            Ok(0)
+        }
+    }
+
+    #[test]
+    fn full_update_split() {
+        for split in 0..=FULL_UPDATE.len() {
+            let (first, second) = FULL_UPDATE.split_at(split);
+            let mut queue = VecDeque::from(first.to_vec());
+
+            if split < FULL_UPDATE.len() {
+                assert!(
+                    decode_message(&mut queue, &mut TestContext)
+                        .unwrap()
+                        .is_none(),
+                    "Decoded a message split at {split}"
+                );
+                assert_eq!(queue.len(), split);
+            }
+
+            queue.extend(second);
+
+            let message = decode_message(&mut queue, &mut TestContext).unwrap();
+            assert!(
+                matches!(message, Some(ServerMessageGeneric::FullUpdate(_))),
+                "Failed to decode a message split at {split}"
+            );
+            assert!(queue.is_empty());
+        }
+    }
+
     #[test]
-    fn vec_deque_iter() {
-        let mut x = super::VecDequeIter(vec![0, 1, 2, 3, 4].into());
+    fn consecutive_messages() {
+        let (first, second) = FULL_UPDATE.split_at(FULL_UPDATE.len() / 2);
+        let mut queue = VecDeque::from(FULL_UPDATE.to_vec());
+        queue.extend(first);
+
+        assert!(
+            decode_message(&mut queue, &mut TestContext)
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            decode_message(&mut queue, &mut TestContext)
+                .unwrap()
+                .is_none()
+        );
+
+        queue.extend(second);
+
+        assert!(
+            decode_message(&mut queue, &mut TestContext)
+                .unwrap()
+                .is_some()
+        );
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn purge_on_protocol_error() {
+        // Not a packet kind
+        let mut queue = VecDeque::from([u8::MAX]);
+        queue.extend(FULL_UPDATE);
+
+        assert!(decode_message(&mut queue, &mut TestContext).is_err());
+        assert!(queue.is_empty());
+    }
+
+    /// Waits for the connect task to hand over the stream
+    async fn wait_connected(client: &mut NetworkClient) {
+        while client.server_connection.is_none() {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            client.handle_connection_event();
+        }
+    }
+
+    /// Plays the server's side of the reward handshake
+    #[tokio::test]
+    async fn reward_handshake() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let reward_key = [7; 16];
+        let challenge = [42; 16];
+
+        let mut client = NetworkClient::default();
+        client.connect(&listener.local_addr().unwrap().to_string());
+        wait_connected(&mut client).await;
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        client
+            .send_message(ClientRewardRequest {
+                reward_name: "Ferris".parse().unwrap(),
+            })
+            .await
+            .unwrap();
+
+        let mut request = [0; 8];
+        server.read_exact(&mut request).await.unwrap();
+        assert_eq!(request[0], u8::from(PacketKind::RewardRequest));
+        assert_eq!(&request[1..], b"\x06Ferris");
+
+        server
+            .write_all(&[PacketKind::RewardChallengeCode.into()])
+            .await
+            .unwrap();
+        server.write_all(&challenge).await.unwrap();
+
+        let message = loop {
+            let message =
+                decode_message(&mut *client.receive_message.lock().await, &mut TestContext)
+                    .unwrap();
 
-        assert_eq!(x.next(), Some(0));
-        assert_eq!(x.next(), Some(1));
+            if let Some(message) = message {
+                break message;
+            }
+
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        };
+
+        let ServerMessageGeneric::RewardChallengeCode(message) = message else {
+            panic!("Expected reward challenge");
+        };
+
+        client
+            .answer_reward_challenge(&reward_key, &message.challenge)
+            .await
+            .unwrap();
+
+        let mut response = [0; 17];
+        server.read_exact(&mut response).await.unwrap();
+        assert_eq!(response[0], u8::from(PacketKind::RewardChallengeResponse));
+        assert_eq!(
+            response[1..],
+            reward_response(&reward_key, &challenge, Ipv4Addr::LOCALHOST.into())
+        );
+    }
 
-        x.extend([100, 200]);
+    #[tokio::test]
+    async fn wrong_password_rejoins() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
 
-        assert_eq!(x.next(), Some(2));
-        assert_eq!(x.next(), Some(3));
-        assert_eq!(x.next(), Some(4));
-        assert_eq!(x.next(), Some(100));
-        assert_eq!(x.next(), Some(200));
-        assert_eq!(x.next(), None);
+        let mut client = NetworkClient::default();
+        client.connect(&listener.local_addr().unwrap().to_string());
+        wait_connected(&mut client).await;
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        client
+            .send_password("hunter2".parse().unwrap(), true)
+            .await
+            .unwrap();
+
+        // Passwords are sent without a packet kind
+        let mut password = [0; 8];
+        server.read_exact(&mut password).await.unwrap();
+        assert_eq!(&password, b"\x07hunter2");
+        assert!(
+            client
+                .sent_password
+                .as_ref()
+                .is_some_and(|sent| sent.remember)
+        );
+
+        client.disconnect(DisconnectReason::PasswordWrong);
+        assert!(client.password_rejected);
+        assert!(client.sent_password.is_none());
+
+        client.rejoin().unwrap();
+        wait_connected(&mut client).await;
+        listener.accept().await.unwrap();
+
+        assert_eq!(
+            client.connection_state,
+            NetworkingState::AttemptingConnection
+        );
+        assert!(client.password_rejected);
+        assert!(matches!(
+            client.network_events.receiver.try_recv(),
+            Ok(ClientNetworkEvent::Connected)
+        ));
+    }
+
+    #[tokio::test]
+    async fn refused_connection_fails() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        let mut client = NetworkClient::default();
+        client.connect(&address);
+
+        let event = client.network_events.receiver.recv().await;
+        assert!(matches!(
+            event,
+            Some(ClientNetworkEvent::ConnectFailed(NetworkError::Connection(
+                ..
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn quiet_server_times_out() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+
+        let mut client = NetworkClient::default();
+        client.connect(&listener.local_addr().unwrap().to_string());
+        wait_connected(&mut client).await;
+        let (mut server, _) = listener.accept().await.unwrap();
+
+        server.write_all(&[PacketKind::Ping.into()]).await.unwrap();
+        while client.receive_message.lock().await.is_empty() {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+
+        let now = Instant::now();
+        client.check_timeout(now);
+        assert!(client.server_connection.is_some());
+
+        client.password_prompt = Some(PasswordPrompt::default());
+        client.check_timeout(now + RECEIVE_TIMEOUT);
+        assert!(client.server_connection.is_some());
+
+        client.password_prompt = None;
+        client.check_timeout(now + RECEIVE_TIMEOUT);
+        assert_eq!(
+            client.connection_state,
+            NetworkingState::Disconnected(Some(DisconnectReason::TimedOut))
+        );
     }
 }
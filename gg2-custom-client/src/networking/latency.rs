@@ -0,0 +1,166 @@
+use std::{fmt::Display, time::Duration};
+
+use tokio::time::Instant;
+
+/// How often the client pings the server
+pub const PING_INTERVAL: Duration = Duration::from_secs(1);
+/// How long a ping waits for its reply before it's given up on
+const PING_TIMEOUT: Duration = Duration::from_secs(5);
+/// How far each round trip moves the estimates, as in RFC 6298
+const ROUND_TRIP_GAIN: f32 = 1.0 / 8.0;
+const JITTER_GAIN: f32 = 1.0 / 4.0;
+
+/// Rolling round trip estimates from answered pings
+#[derive(Debug, Default, Clone)]
+pub struct Latency {
+    /// When the unanswered ping was sent
+    pending: Option<Instant>,
+    last_sent: Option<Instant>,
+    /// Unknown until the first ping is answered
+    round_trip: Option<Duration>,
+    /// How much round trips vary from the estimate
+    jitter: Duration,
+}
+
+impl Latency {
+    #[inline]
+    #[must_use]
+    pub fn round_trip(&self) -> Option<Duration> {
+        self.round_trip
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    /// Whether another ping is due, only one is sent at a time
+    #[must_use]
+    pub fn should_ping(&mut self, now: Instant) -> bool {
+        !self.awaiting_reply(now)
+            && self
+                .last_sent
+                .is_none_or(|sent| now.duration_since(sent) >= PING_INTERVAL)
+    }
+
+    pub fn ping_sent(&mut self, now: Instant) {
+        self.pending = Some(now);
+        self.last_sent = Some(now);
+    }
+
+    /// Whether a ping from the server would answer one the client sent
+    ///
+    /// Pings carry nothing to tell a reply from the server's own ping, so only
+    /// the first one while the client's ping is unanswered counts as its reply.
+    /// Replies that never came are given up on, so later pings are answered.
+    #[must_use]
+    pub fn awaiting_reply(&mut self, now: Instant) -> bool {
+        self.pending = self
+            .pending
+            .filter(|sent| now.duration_since(*sent) < PING_TIMEOUT);
+
+        self.pending.is_some()
+    }
+
+    /// Records the reply to the client's ping, if it's still waiting for one
+    pub fn reply_received(&mut self, now: Instant) {
+        if self.awaiting_reply(now)
+            && let Some(sent) = self.pending.take()
+        {
+            self.record(now.duration_since(sent));
+        }
+    }
+
+    fn record(&mut self, sample: Duration) {
+        let Some(round_trip) = self.round_trip else {
+            self.round_trip = Some(sample);
+            self.jitter = sample / 2;
+            return;
+        };
+
+        self.jitter = self.jitter.mul_f32(1.0 - JITTER_GAIN)
+            + round_trip.abs_diff(sample).mul_f32(JITTER_GAIN);
+        self.round_trip =
+            Some(round_trip.mul_f32(1.0 - ROUND_TRIP_GAIN) + sample.mul_f32(ROUND_TRIP_GAIN));
+    }
+}
+
+impl Display for Latency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.round_trip {
+            Some(round_trip) => write!(
+                f,
+                "{:.0}ms ± {:.0}ms",
+                round_trip.as_secs_f32() * 1000.0,
+                self.jitter.as_secs_f32() * 1000.0
+            ),
+            None => write!(f, "Unknown"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(latency: &mut Latency, now: Instant, round_trip_ms: u64) -> Instant {
+        assert!(latency.should_ping(now));
+        latency.ping_sent(now);
+        assert!(!latency.should_ping(now + PING_INTERVAL));
+
+        let answered = now + Duration::from_millis(round_trip_ms);
+        assert!(latency.awaiting_reply(answered));
+        latency.reply_received(answered);
+        assert!(!latency.awaiting_reply(answered));
+
+        now + PING_INTERVAL
+    }
+
+    #[test]
+    fn rolling_estimate() {
+        let mut latency = Latency::default();
+        let mut now = Instant::now();
+        assert_eq!(latency.to_string(), "Unknown");
+
+        now = answer(&mut latency, now, 80);
+        assert_eq!(latency.round_trip(), Some(Duration::from_millis(80)));
+        assert_eq!(latency.jitter(), Duration::from_millis(40));
+
+        // A steady connection settles its jitter
+        for _ in 0..50 {
+            now = answer(&mut latency, now, 80);
+        }
+        assert_eq!(latency.to_string(), "80ms ± 0ms");
+
+        // A single spike moves the estimate only a little
+        answer(&mut latency, now, 160);
+        assert_eq!(latency.to_string(), "90ms ± 20ms");
+    }
+
+    #[test]
+    fn unprompted_ping() {
+        let mut latency = Latency::default();
+        let now = Instant::now();
+
+        assert!(!latency.awaiting_reply(now));
+        latency.reply_received(now);
+        assert_eq!(latency.round_trip(), None);
+    }
+
+    #[test]
+    fn lost_reply_is_given_up() {
+        let mut latency = Latency::default();
+        let now = Instant::now();
+
+        latency.ping_sent(now);
+        assert!(!latency.should_ping(now + PING_INTERVAL));
+
+        // Pings after the timeout are the server's own
+        let late = now + PING_TIMEOUT;
+        assert!(!latency.awaiting_reply(late));
+        assert!(latency.should_ping(late));
+        latency.reply_received(late);
+        assert_eq!(latency.round_trip(), None);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::prelude::*;
+
+/// Asks the player for the server's password while the handshake waits
+#[derive(Debug, Default)]
+pub struct PasswordPrompt {
+    /// What the player has typed so far
+    pub input: String,
+    /// Save the password for the server once it's accepted
+    pub remember: bool,
+    /// The last password sent was wrong
+    pub wrong: bool,
+    /// The player is done typing
+    pub submitted: bool,
+}
+
+/// A password waiting on the server's answer
+#[derive(Debug)]
+pub struct SentPassword {
+    pub password: GGStringShort,
+    pub remember: bool,
+}
+
+impl ClientGame {
+    /// The password given on the command line, otherwise the one saved for the server
+    async fn known_password(&self, server_address: Option<&str>) -> Option<GGStringShort> {
+        if let Some(ClientCliSubcommand::JoinServer(join_server)) =
+            &self.world.client_cli_arguments().command
+            && let Some(password) = &join_server.password
+        {
+            return Some(password.clone());
+        }
+
+        self.world
+            .config()
+            .read()
+            .await
+            .networking
+            .server_passwords
+            .get(server_address?)
+            .cloned()
+    }
+
+    /// Answers with a known password, or asks the player for one
+    ///
+    /// Known passwords aren't tried again once one was wrong
+    pub(super) async fn request_password(
+        &self,
+        network_client: &mut NetworkClient,
+    ) -> Result<(), ClientError> {
+        if !network_client.password_rejected
+            && let Some(password) = self.known_password(network_client.server_address()).await
+        {
+            debug!("Sending known password to server");
+            network_client.send_password(password, false).await?;
+            return Ok(());
+        }
+
+        debug!("Waiting on password from player");
+        network_client.password_prompt = Some(PasswordPrompt {
+            wrong: network_client.password_rejected,
+            ..Default::default()
+        });
+
+        Ok(())
+    }
+
+    /// Sends the password once the player submits it
+    pub(super) async fn submit_password(
+        &self,
+        network_client: &mut NetworkClient,
+    ) -> Result<(), ClientError> {
+        let Some(prompt) = network_client.password_prompt.as_mut() else {
+            return Ok(());
+        };
+
+        if !prompt.submitted {
+            return Ok(());
+        }
+
+        match GGStringShort::try_from(prompt.input.clone()) {
+            Ok(password) => {
+                let remember = prompt.remember;
+                network_client.password_prompt = None;
+
+                debug!("Sending password to server...");
+                network_client.send_password(password, remember).await?;
+            }
+            Err(error) => {
+                warn!("Password can't be sent: {error}");
+                prompt.submitted = false;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Saves the accepted password if the player asked to
+    pub(super) async fn password_accepted(&self, network_client: &mut NetworkClient) {
+        network_client.password_rejected = false;
+
+        let Some(sent) = network_client.sent_password.take() else {
+            return;
+        };
+
+        let Some(server_address) = network_client.server_address() else {
+            return;
+        };
+
+        if sent.remember {
+            let mut config = self.world.config().write().await;
+            config
+                .networking
+                .server_passwords
+                .insert(server_address.to_string(), sent.password);
+
+            if let Err(error) = config.save() {
+                error!("Failed to save server password: {error}");
+            }
+        }
+    }
+}
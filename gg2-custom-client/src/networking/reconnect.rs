@@ -0,0 +1,122 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::prelude::*;
+
+/// The wait before the first reconnect, doubled for each one after
+const RECONNECT_DELAY_BASE: Duration = Duration::from_secs(1);
+const RECONNECT_DELAY_MAX: Duration = Duration::from_secs(30);
+
+/// What to do about a connection that can be retried
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconnectPoll {
+    /// Still backing off
+    Wait,
+    /// Start this attempt now
+    Attempt(u32),
+    /// Every attempt allowed has been used
+    GiveUp,
+}
+
+/// Backs off between attempts to get back to the last server
+#[derive(Debug, Clone, Default)]
+pub struct Reconnect {
+    /// Attempts made since the last successful join
+    pub attempt: u32,
+    /// When the next attempt starts, once one is scheduled
+    pub next_attempt: Option<Instant>,
+}
+
+impl Reconnect {
+    /// How long to wait before the attempt after `attempt`
+    pub fn delay(attempt: u32) -> Duration {
+        RECONNECT_DELAY_BASE
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(RECONNECT_DELAY_MAX)
+    }
+
+    pub fn poll(&mut self, now: Instant, max_attempts: u32) -> ReconnectPoll {
+        if self.attempt >= max_attempts {
+            self.next_attempt = None;
+            return ReconnectPoll::GiveUp;
+        }
+
+        match self.next_attempt {
+            None => {
+                self.next_attempt = Some(now + Self::delay(self.attempt));
+                ReconnectPoll::Wait
+            }
+            Some(next_attempt) if now >= next_attempt => {
+                self.attempt += 1;
+                self.next_attempt = None;
+                ReconnectPoll::Attempt(self.attempt)
+            }
+            Some(_) => ReconnectPoll::Wait,
+        }
+    }
+}
+
+impl ClientGame {
+    /// Goes back to the last server once it has backed off long enough
+    pub(super) async fn reconnect(
+        &self,
+        network_client: &mut NetworkClient,
+    ) -> Result<(), ClientError> {
+        let max_attempts = self
+            .world
+            .config()
+            .read()
+            .await
+            .networking
+            .reconnect_attempts;
+
+        match network_client.reconnect.poll(Instant::now(), max_attempts) {
+            ReconnectPoll::Wait | ReconnectPoll::GiveUp => (),
+            ReconnectPoll::Attempt(attempt) => {
+                info!("Reconnecting: attempt {attempt} of {max_attempts}");
+                network_client.rejoin()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_doubles_up_to_max() {
+        assert_eq!(Reconnect::delay(0), Duration::from_secs(1));
+        assert_eq!(Reconnect::delay(3), Duration::from_secs(8));
+        assert_eq!(Reconnect::delay(10), RECONNECT_DELAY_MAX);
+        assert_eq!(Reconnect::delay(u32::MAX), RECONNECT_DELAY_MAX);
+    }
+
+    #[test]
+    fn backs_off_then_gives_up() {
+        let mut reconnect = Reconnect::default();
+        let now = Instant::now();
+
+        assert_eq!(reconnect.poll(now, 2), ReconnectPoll::Wait);
+        assert_eq!(reconnect.poll(now, 2), ReconnectPoll::Wait);
+        assert_eq!(
+            reconnect.poll(now + Duration::from_secs(1), 2),
+            ReconnectPoll::Attempt(1)
+        );
+
+        let now = now + Duration::from_secs(1);
+        assert_eq!(reconnect.poll(now, 2), ReconnectPoll::Wait);
+        assert_eq!(
+            reconnect.poll(now + Duration::from_secs(1), 2),
+            ReconnectPoll::Wait
+        );
+        assert_eq!(
+            reconnect.poll(now + Duration::from_secs(2), 2),
+            ReconnectPoll::Attempt(2)
+        );
+        assert_eq!(reconnect.poll(now, 2), ReconnectPoll::GiveUp);
+    }
+}
@@ -0,0 +1,37 @@
+use std::net::IpAddr;
+
+use hmac::{Hmac, Mac};
+use md5::Md5;
+
+/// Proves the client holds its reward key without sending it
+///
+/// The server's address is mixed in so a response can't be replayed to another server
+pub fn reward_response(reward_key: &[u8], challenge: &[u8], server_ip: IpAddr) -> [u8; 16] {
+    let mut mac = Hmac::<Md5>::new_from_slice(reward_key).expect("HMAC accepts keys of any length");
+    mac.update(challenge);
+    mac.update(server_ip.to_string().as_bytes());
+
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[test]
+    fn response_includes_address() {
+        let reward_key = std::array::from_fn::<u8, 16, _>(|index| index as u8);
+        let challenge = std::array::from_fn::<u8, 16, _>(|index| index as u8 + 16);
+
+        assert_eq!(
+            hex::encode(reward_response(
+                &reward_key,
+                &challenge,
+                Ipv4Addr::LOCALHOST.into()
+            )),
+            "2454efbac7530bb9e3bf653d99ace8a7"
+        );
+    }
+}
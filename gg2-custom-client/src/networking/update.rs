@@ -1,9 +1,11 @@
+use tokio::time::Instant;
+
 use crate::prelude::*;
 
 use super::io::ClientNetworkEvent;
 
 impl NetworkClient {
-    async fn handle_network_events(&mut self) -> Result<(), ClientError> {
+    async fn handle_network_events(&mut self, world: &ClientWorld) -> Result<(), ClientError> {
         if let Ok(event) = self.network_events.receiver.try_recv() {
             match event {
                 ClientNetworkEvent::Connected => {
@@ -12,9 +14,15 @@ impl NetworkClient {
                     self.send_message(ClientHello::default()).await?;
                     self.connection_state = NetworkingState::AwaitingHello;
                 }
-                ClientNetworkEvent::Disconnected => {
+                ClientNetworkEvent::ConnectFailed(error) => {
+                    warn!("{error}");
+                    self.connection_state =
+                        NetworkingState::Disconnected(Some(DisconnectReason::Unreachable));
+                }
+                ClientNetworkEvent::Disconnected(reason) => {
                     debug!("Network Event: Disconnected from server");
-                    self.disconnect();
+                    let reason = self.final_reason(world).await.unwrap_or(reason);
+                    self.disconnect(reason);
                 }
                 ClientNetworkEvent::Error(error) => Err(error)?,
             }
@@ -22,16 +30,78 @@ impl NetworkClient {
 
         Ok(())
     }
+
+    /// Looks through what arrived before the connection closed for why it did
+    async fn final_reason(&self, world: &ClientWorld) -> Option<DisconnectReason> {
+        while let Ok(Some(message)) = self.pop_message(world).await {
+            if let Some(reason) = DisconnectReason::from_message(&message) {
+                return Some(reason);
+            }
+        }
+
+        None
+    }
+
+    /// Pops the next message unless it ends the connection
+    async fn pop_message_connected(
+        &mut self,
+        world: &ClientWorld,
+    ) -> Result<Option<ServerMessageGeneric>, ClientError> {
+        let message = self.pop_message(world).await?;
+
+        if let Some(reason) = message.as_ref().and_then(DisconnectReason::from_message) {
+            self.disconnect(reason);
+            return Ok(None);
+        }
+
+        Ok(message)
+    }
 }
 
 impl ClientGame {
+    async fn reserve_slot(&self, network_client: &mut NetworkClient) -> Result<(), ClientError> {
+        debug!("Reserving player slot");
+
+        let player_name = {
+            let config = self.world.config().read().await;
+            config.game.player_name.clone()
+        };
+
+        network_client
+            .send_message(ClientReserveSlot { player_name })
+            .await?;
+        network_client.connection_state = NetworkingState::ReserveSlot;
+
+        Ok(())
+    }
+
+    /// Connects to a server picked while playing
+    pub async fn join_server(&self, address: &str) {
+        let mut network_client = self.world.network_client().write().await;
+
+        network_client.reconnect = Reconnect::default();
+        network_client.connect(address);
+        network_client.connection_state = NetworkingState::AttemptingConnection;
+    }
+
     pub async fn update_network_client(&self) -> Result<(), ClientError> {
         let mut network_client = self.world.network_client().write().await;
         network_client.handle_connection_event();
-        network_client.handle_network_events().await?;
+        network_client.handle_network_events(self.world).await?;
+        network_client.check_timeout(Instant::now());
 
         match network_client.connection_state {
-            NetworkingState::Disconnected => {
+            // Asks for the password again rather than giving up
+            NetworkingState::Disconnected(Some(DisconnectReason::PasswordWrong)) => {
+                info!("Wrong password; reconnecting");
+                network_client.rejoin()?;
+            }
+            NetworkingState::Disconnected(Some(reason)) if reason.is_retryable() => {
+                self.reconnect(&mut network_client).await?;
+            }
+            // Stays disconnected once a connection has ended
+            NetworkingState::Disconnected(Some(_)) => (),
+            NetworkingState::Disconnected(None) => {
                 if let Some(command) = &self.world.client_cli_arguments().command {
                     match command {
                         ClientCliSubcommand::JoinServer(join_server) => {
@@ -48,56 +118,116 @@ impl ClientGame {
                                 }
                             };
 
-                            network_client.connect(url).await?;
+                            network_client.reconnect = Reconnect::default();
+                            network_client.connect(url);
                             network_client.connection_state = NetworkingState::AttemptingConnection;
                         }
+                        // Exits before the game starts
+                        ClientCliSubcommand::Probe(_) => (),
                     }
                 }
             }
             // Handled in `Self::handle_network_events`
             NetworkingState::AttemptingConnection => (),
             NetworkingState::AwaitingHello => {
-                if let Some(generic_message) = network_client.pop_message(self.world).await? {
+                self.submit_password(&mut network_client).await?;
+
+                if let Some(generic_message) =
+                    network_client.pop_message_connected(self.world).await?
+                {
                     match generic_message {
                         ServerMessageGeneric::Hello(message) => {
                             debug!("{message:#?}");
-                            debug!("Reserving player slot");
+                            self.password_accepted(&mut network_client).await;
 
-                            let player_name = {
-                                let config = self.world.config().read().await;
-                                config.game.player_name.clone()
-                            };
+                            let mut plugin_registry = self.world.plugin_registry().write().await;
+                            let unsupported = plugin_registry
+                                .claim_server_plugins(&message.plugins, message.plugins_required)?;
 
-                            network_client
-                                .send_message(ClientReserveSlot { player_name })
-                                .await?;
-                            network_client.connection_state = NetworkingState::ReserveSlot;
-                        }
-                        ServerMessageGeneric::IncompatibleProtocol(_) => {
-                            error!("Server doesn't support client's protocol; disconnecting...");
-                            network_client.disconnect();
+                            if !unsupported.is_empty() {
+                                let names = unsupported
+                                    .iter()
+                                    .map(|plugin| plugin.name.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join(", ");
+
+                                if message.plugins_required {
+                                    warn!("Server requires unsupported plugins: {names}");
+                                    network_client.disconnect(DisconnectReason::UnsupportedPlugins);
+                                    return Ok(());
+                                }
+
+                                debug!("Server runs unsupported plugins: {names}");
+                            }
+                            drop(plugin_registry);
+
+                            match message.map_md5 {
+                                Some(map_md5)
+                                    if !self
+                                        .map_available(&message.map_name, message.map_md5)
+                                        .await? =>
+                                {
+                                    info!("Downloading map: {}", message.map_name);
+
+                                    network_client.map_download =
+                                        Some(self.world.map_cache().begin_download(map_md5).await?);
+                                    network_client.send_message(ClientDownloadMap).await?;
+                                    network_client.connection_state =
+                                        NetworkingState::DownloadingMap;
+                                }
+                                _ => self.reserve_slot(&mut network_client).await?,
+                            }
                         }
                         ServerMessageGeneric::PasswordRequest(_) => {
-                            // TODO: Add password prompt
-                            let password = GGStringShort::try_from("1234".to_string()).unwrap();
-                            debug!("Sending password to server...");
-                            network_client.send(&password).await?;
-                        }
-                        ServerMessageGeneric::PasswordWrong(_) => {
-                            error!("Server password is wrong");
-                            network_client.disconnect();
+                            self.request_password(&mut network_client).await?;
                         }
                         _ => Err(NetworkError::IncorrectMessage(generic_message.into()))?,
                     }
                 }
             }
+            NetworkingState::DownloadingMap => {
+                let started = network_client
+                    .map_download
+                    .as_ref()
+                    .is_some_and(MapDownload::is_started);
+
+                if !started {
+                    if let Some(generic_message) =
+                        network_client.pop_message_connected(self.world).await?
+                    {
+                        match generic_message {
+                            ServerMessageGeneric::DownloadMap(message) => {
+                                debug!("Receiving {} bytes of map", message.length);
+
+                                if let Some(download) = network_client.map_download.as_mut()
+                                    && let Err(error) = download.start(message.length)
+                                {
+                                    error!("{error}");
+                                    network_client.disconnect(DisconnectReason::MapDownload);
+                                }
+                            }
+                            _ => Err(NetworkError::IncorrectMessage(generic_message.into()))?,
+                        }
+                    }
+                } else {
+                    match network_client.download_map_bytes().await {
+                        Ok(true) => {
+                            info!("Map downloaded");
+                            self.reserve_slot(&mut network_client).await?;
+                        }
+                        Ok(false) => (),
+                        Err(error) => {
+                            error!("{error}");
+                            network_client.disconnect(DisconnectReason::MapDownload);
+                        }
+                    }
+                }
+            }
             NetworkingState::ReserveSlot => {
-                if let Some(generic_message) = network_client.pop_message(self.world).await? {
+                if let Some(generic_message) =
+                    network_client.pop_message_connected(self.world).await?
+                {
                     match generic_message {
-                        ServerMessageGeneric::ServerFull(_) => {
-                            info!("Server full");
-                            network_client.disconnect();
-                        }
                         ServerMessageGeneric::ReserveSlot(_) => {
                             debug!("Reserved player slot; joining");
                             network_client.send_message(ClientPlayerJoin).await?;
@@ -108,18 +238,38 @@ impl ClientGame {
                 }
             }
             NetworkingState::PlayerJoining => {
-                if let Some(generic_message) = network_client.pop_message(self.world).await? {
+                if let Some(generic_message) =
+                    network_client.pop_message_connected(self.world).await?
+                {
                     match generic_message {
                         ServerMessageGeneric::JoinUpdate(message) => {
                             info!("Successfully joined server");
                             debug!("{message:#?}");
 
-                            self.world
-                                .client_players()
-                                .write()
-                                .await
-                                .set_client_player(message.client_player_id);
+                            // Every player is sent again after joining
+                            let mut players = self.world.client_players().write().await;
+                            *players = ClientPlayers::default();
+                            players.set_client_player(message.client_player_id);
+                            drop(players);
+
+                            let (client_settings, reward_credentials) = {
+                                let config = self.world.config().read().await;
+                                (
+                                    ClientClientSettings::from(&config.settings),
+                                    config.game.reward_credentials(),
+                                )
+                            };
+
+                            network_client.send_message(client_settings).await?;
 
+                            if let Some((reward_name, _)) = reward_credentials {
+                                debug!("Requesting rewards for {reward_name}");
+                                network_client
+                                    .send_message(ClientRewardRequest { reward_name })
+                                    .await?;
+                            }
+
+                            network_client.reconnect = Reconnect::default();
                             network_client.connection_state = NetworkingState::InGame;
                         }
                         _ => Err(NetworkError::IncorrectMessage(generic_message.into()))?,
@@ -127,9 +277,74 @@ impl ClientGame {
                 }
             }
             NetworkingState::InGame => {
-                if let Some(generic_message) = network_client.pop_message(self.world).await? {
-                    self.server_message(generic_message).await?;
+                let now = Instant::now();
+
+                if let Some(generic_message) =
+                    network_client.pop_message_connected(self.world).await?
+                {
+                    match generic_message {
+                        // Answered here as it needs the connection
+                        ServerMessageGeneric::RewardChallengeCode(message) => {
+                            let reward_credentials =
+                                self.world.config().read().await.game.reward_credentials();
+
+                            match reward_credentials {
+                                Some((_, reward_key)) => {
+                                    debug!("Answering reward challenge");
+                                    network_client
+                                        .answer_reward_challenge(&reward_key, &message.challenge)
+                                        .await?;
+                                }
+                                None => warn!("Sent a reward challenge without a reward key"),
+                            }
+                        }
+                        ServerMessageGeneric::PluginPacket(message) => {
+                            let mut plugin_registry = self.world.plugin_registry().write().await;
+                            let result = plugin_registry.route(message);
+
+                            match result {
+                                Ok(()) => (),
+                                // Packets for optional plugins are dropped
+                                Err(error @ PluginError::Unclaimed(_))
+                                    if plugin_registry.required() =>
+                                {
+                                    warn!("{error}");
+                                    network_client.disconnect(DisconnectReason::UnknownPlugin);
+                                    return Ok(());
+                                }
+                                Err(error @ PluginError::Unclaimed(_)) => info!("{error}"),
+                                Err(error) => warn!("{error}"),
+                            }
+                        }
+                        ServerMessageGeneric::Ping(_)
+                            if network_client.latency.awaiting_reply(now) =>
+                        {
+                            network_client.latency.reply_received(now);
+                        }
+                        // The server's own ping
+                        ServerMessageGeneric::Ping(_) => {
+                            network_client.send_message(ClientPing).await?;
+                        }
+                        // Maps are only downloaded while joining
+                        ServerMessageGeneric::ChangeMap(message)
+                            if !self
+                                .map_available(&message.map_name, message.map_md5)
+                                .await? =>
+                        {
+                            info!("Rejoining to download map: {}", message.map_name);
+                            network_client.rejoin()?;
+                            return Ok(());
+                        }
+                        generic_message => self.server_message(generic_message).await?,
+                    }
+                }
+
+                // Sent after handling what's already arrived, which can't be its reply
+                if network_client.latency.should_ping(now) {
+                    network_client.send_message(ClientPing).await?;
+                    network_client.latency.ping_sent(now);
                 }
+
                 drop(network_client);
                 if self
                     .world
@@ -0,0 +1,49 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::prelude::*;
+
+/// How long a notice stays on screen
+const NOTICE_DURATION: Duration = Duration::from_secs(5);
+
+/// A short message shown to the player for a few seconds
+#[derive(Debug, Clone)]
+pub struct Notice {
+    pub message: String,
+    expires: Instant,
+}
+
+impl Notice {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            expires: Instant::now() + NOTICE_DURATION,
+        }
+    }
+
+    #[inline]
+    pub fn expired(&self) -> bool {
+        Instant::now() >= self.expires
+    }
+}
+
+impl ClientGame {
+    /// Warns the player about an upcoming balance or that they were moved
+    pub async fn event_balance(&self, message: &ServerBalance) {
+        let text = match message.player_id {
+            None => "Teams will be balanced soon",
+            Some(player_id) => {
+                let client_id = self.world.client_players().read().await.get_client_id();
+
+                if client_id.ok() != Some(player_id) {
+                    return;
+                }
+
+                "You have been moved to the other team"
+            }
+        };
+
+        *self.world.notice().write().await = Some(Notice::new(text));
+    }
+}
@@ -19,6 +19,11 @@ impl SpriteRenderable for Player {
     fn get_class(&self) -> Option<ClassGeneric> {
         Some(self.class)
     }
+
+    #[inline]
+    fn get_invulnerable(&self) -> Option<bool> {
+        Some(self.ubered)
+    }
 }
 
 #[derive(Debug, Default)]
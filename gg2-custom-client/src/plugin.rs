@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use gg2_common::networking::plugin::ServerPlugin;
+
+use crate::prelude::*;
+
+pub mod error;
+
+pub type PluginHandlerError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Rust-side support for one of the server's plugins
+pub trait PluginHandler: Send + Sync {
+    /// The name the server lists the plugin under
+    fn name(&self) -> &str;
+
+    fn handle_packet(&mut self, payload: &[u8]) -> Result<(), PluginHandlerError>;
+}
+
+/// The handlers built into the client
+fn builtin_handlers() -> Vec<Box<dyn PluginHandler>> {
+    // No plugins are built into the client yet, so every server plugin goes unclaimed
+    Vec::new()
+}
+
+/// Routes plugin packets to the handlers that claimed their id
+#[derive(Default)]
+pub struct PluginRegistry {
+    handlers: Vec<Box<dyn PluginHandler>>,
+    /// Indices into `handlers` by the plugin id they claimed
+    claimed: HashMap<u8, usize>,
+    /// The server refuses clients without its plugins
+    required: bool,
+}
+
+impl PluginRegistry {
+    /// A registry holding the handlers built into the client
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+
+        for handler in builtin_handlers() {
+            if let Err(error) = registry.register(handler) {
+                error!("{error}");
+            }
+        }
+
+        registry
+    }
+
+    /// Adds a handler, rejecting it when another has the same name
+    pub fn register(&mut self, handler: Box<dyn PluginHandler>) -> Result<(), PluginError> {
+        if self
+            .handlers
+            .iter()
+            .any(|other| other.name() == handler.name())
+        {
+            return Err(PluginError::AlreadyRegistered(handler.name().to_string()));
+        }
+
+        self.handlers.push(handler);
+
+        Ok(())
+    }
+
+    /// Claims ids for the server's plugins, returning those without a handler
+    ///
+    /// Plugins are given ids in the order the server lists them
+    pub fn claim_server_plugins<'a>(
+        &mut self,
+        plugins: &'a [ServerPlugin],
+        required: bool,
+    ) -> Result<Vec<&'a ServerPlugin>, PluginError> {
+        self.claimed.clear();
+        self.required = required;
+        let mut unsupported = Vec::new();
+
+        for (plugin_id, plugin) in plugins.iter().enumerate() {
+            let plugin_id = u8::try_from(plugin_id).map_err(|_| PluginError::TooMany)?;
+
+            match self
+                .handlers
+                .iter()
+                .position(|handler| handler.name() == plugin.name)
+            {
+                Some(index) => {
+                    self.claimed.insert(plugin_id, index);
+                }
+                None => unsupported.push(plugin),
+            }
+        }
+
+        Ok(unsupported)
+    }
+
+    #[inline]
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    pub fn route(&mut self, message: ServerPluginPacket) -> Result<(), PluginError> {
+        let index = *self
+            .claimed
+            .get(&message.plugin_id)
+            .ok_or(PluginError::Unclaimed(message.plugin_id))?;
+
+        self.handlers[index]
+            .handle_packet(&message.payload)
+            .map_err(|error| PluginError::Handler(message.plugin_id, error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// Keeps every payload it's sent
+    struct RecordingHandler {
+        name: &'static str,
+        received: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    impl RecordingHandler {
+        fn new(name: &'static str) -> Self {
+            Self {
+                name,
+                received: Arc::default(),
+            }
+        }
+    }
+
+    impl PluginHandler for RecordingHandler {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn handle_packet(&mut self, payload: &[u8]) -> Result<(), PluginHandlerError> {
+            self.received.lock().unwrap().push(payload.to_vec());
+            Ok(())
+        }
+    }
+
+    fn server_plugin(name: &str) -> ServerPlugin {
+        ServerPlugin {
+            name: name.to_string(),
+            md5: 0,
+        }
+    }
+
+    fn packet(plugin_id: u8, payload: &[u8]) -> ServerPluginPacket {
+        ServerPluginPacket {
+            plugin_id,
+            payload: payload.to_vec(),
+        }
+    }
+
+    #[test]
+    fn route_to_claimed_handler() {
+        let handler = RecordingHandler::new("kothtimer");
+        let received = Arc::clone(&handler.received);
+
+        let mut registry = PluginRegistry::default();
+        registry.register(Box::new(handler)).unwrap();
+
+        let plugins = [server_plugin("HatPack"), server_plugin("kothtimer")];
+        let unsupported = registry.claim_server_plugins(&plugins, false).unwrap();
+        assert_eq!(unsupported, [&plugins[0]]);
+
+        registry.route(packet(1, b"first")).unwrap();
+        registry.route(packet(1, b"second")).unwrap();
+        assert_eq!(
+            *received.lock().unwrap(),
+            [b"first".to_vec(), b"second".to_vec()]
+        );
+
+        assert!(matches!(
+            registry.route(packet(0, b"")),
+            Err(PluginError::Unclaimed(0))
+        ));
+    }
+
+    #[test]
+    fn reject_duplicate_names() {
+        let mut registry = PluginRegistry::default();
+        registry
+            .register(Box::new(RecordingHandler::new("kothtimer")))
+            .unwrap();
+
+        assert!(matches!(
+            registry.register(Box::new(RecordingHandler::new("kothtimer"))),
+            Err(PluginError::AlreadyRegistered(name)) if name == "kothtimer"
+        ));
+    }
+}
@@ -0,0 +1,11 @@
+#[derive(Debug, thiserror::Error)]
+pub enum PluginError {
+    #[error("No plugin handler claimed id {0}")]
+    Unclaimed(u8),
+    #[error("A plugin handler named {0} is already registered")]
+    AlreadyRegistered(String),
+    #[error("Server has more plugins than can be given ids")]
+    TooMany,
+    #[error("Plugin {0} failed to handle a packet: {1}")]
+    Handler(u8, Box<dyn std::error::Error + Send + Sync>),
+}
@@ -1,5 +1,6 @@
 pub use gg2_client::networking::{
-    message::server::ClientNetworkDeserializeHudMatchTimer, state::NetworkingState,
+    message::server::ClientNetworkDeserializeHud,
+    state::{DisconnectReason, NetworkingState},
 };
 pub use gg2_custom_common::prelude::*;
 pub use serde_with::skip_serializing_none;
@@ -16,7 +17,10 @@ pub use crate::{
     camera::Camera,
     config::ClientConfig,
     error::ClientError,
-    game::{ClientGame, gamemode::ClientGamemodeState},
+    game::{
+        ClientGame,
+        gamemode::{ClientGamemodeState, gamemode_state},
+    },
     init::{
         App,
         cli::{ClientCliArguments, ClientCliSubcommand},
@@ -31,9 +35,19 @@ pub use crate::{
             winit::{WinitInputDevice, WinitInputState},
         },
     },
-    map::MapInfo,
-    networking::io::NetworkClient,
+    map::{
+        MapInfo,
+        cache::{MapCache, MapDownload},
+    },
+    networking::{
+        io::NetworkClient,
+        latency::Latency,
+        password::{PasswordPrompt, SentPassword},
+        reconnect::Reconnect,
+    },
+    notice::Notice,
     player::ClientPlayers,
+    plugin::{PluginHandler, PluginRegistry, error::PluginError},
     render::{instance::SpriteInstance, texture::atlas::TextureAtlas},
     sync::{ClientGameMessage, RenderMessage},
     world::ClientWorld,
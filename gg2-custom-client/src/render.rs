@@ -313,8 +313,8 @@ impl State {
             players
                 .iter()
                 .map(|player| player.render(&self.textures.sprite_atlas, &asset_server))
-                .flat_map(|player| match player {
-                    Ok(player) => player,
+                .flat_map(|sprite| match sprite {
+                    Ok(sprite) => sprite,
                     Err(error) => {
                         error!("Sprite Render: {error}");
                         None
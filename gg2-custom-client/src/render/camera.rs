@@ -4,8 +4,8 @@ use crate::prelude::*;
 impl Camera {
     /// Genrates a matrix to project world space into screen space
     fn build_view_projection_matrix(&self) -> Mat4 {
-        let width_half = super::GAME_WIDTH as f32 / 2.0;
-        let height_half = super::GAME_HEIGHT as f32 / 2.0;
+        let width_half = super::GAME_WIDTH as f32 / 2.0 * self.zoom;
+        let height_half = super::GAME_HEIGHT as f32 / 2.0 * self.zoom;
 
         Mat4::orthographic_rh_gl(
             self.translation.x - width_half,
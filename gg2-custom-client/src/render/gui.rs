@@ -5,6 +5,7 @@ use winit::{event::WindowEvent, window::Window};
 
 use crate::prelude::*;
 
+pub mod browser;
 pub mod draw;
 
 const PIXELS_PER_POINT: f32 = 1.0;
@@ -17,6 +18,9 @@ pub struct GuiRenderer {
     world: &'static ClientWorld,
     debug_player_team: Team,
     debug_player_class: ClassGeneric,
+    server_browser: browser::ServerBrowser,
+    settings_open: bool,
+    menu_pressed_last_frame: bool,
 }
 
 impl GuiRenderer {
@@ -54,6 +58,12 @@ impl GuiRenderer {
             world,
             debug_player_team: Team::default(),
             debug_player_class: ClassGeneric::default(),
+            // Nothing else happens without a server to join
+            server_browser: browser::ServerBrowser::new(
+                world.client_cli_arguments().command.is_none(),
+            ),
+            settings_open: false,
+            menu_pressed_last_frame: false,
         }
     }
 
@@ -0,0 +1,308 @@
+use std::{cmp::Ordering, collections::HashMap, net::SocketAddr, time::Duration};
+
+use gg2_client::networking::{
+    lobby::{LobbyServer, fetch_servers},
+    probe::probe,
+};
+use poll_promise::Promise;
+
+use super::GuiRenderer;
+use crate::prelude::*;
+
+const LOBBY_TIMEOUT: Duration = Duration::from_secs(10);
+const PING_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The column servers are ordered by
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ServerSort {
+    #[default]
+    Name,
+    Map,
+    Players,
+    Ping,
+}
+
+impl ServerSort {
+    const ALL: [Self; 4] = [Self::Name, Self::Map, Self::Players, Self::Ping];
+
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Name => "Name",
+            Self::Map => "Map",
+            Self::Players => "Players",
+            Self::Ping => "Ping",
+        }
+    }
+
+    fn compare(self, a: &BrowserRow, b: &BrowserRow) -> Ordering {
+        match self {
+            Self::Name => a
+                .server
+                .name
+                .to_lowercase()
+                .cmp(&b.server.name.to_lowercase()),
+            Self::Map => a.server.map.cmp(&b.server.map),
+            Self::Players => a.server.players.cmp(&b.server.players),
+            // Unknown pings go last
+            Self::Ping => a
+                .ping
+                .unwrap_or(Duration::MAX)
+                .cmp(&b.ping.unwrap_or(Duration::MAX)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BrowserRow<'a> {
+    server: &'a LobbyServer,
+    ping: Option<Duration>,
+}
+
+fn sort_rows(rows: &mut [BrowserRow], sort: ServerSort, descending: bool) {
+    rows.sort_by(|a, b| {
+        let ordering = sort.compare(a, b);
+
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+/// Lists the lobby's servers to pick one to join
+#[derive(Default)]
+pub struct ServerBrowser {
+    pub open: bool,
+    /// Present once the list has been requested
+    servers: Option<Promise<Result<Vec<LobbyServer>, NetworkError>>>,
+    /// How long each server took to answer a probe
+    pings: HashMap<SocketAddr, Promise<Option<Duration>>>,
+    sort: ServerSort,
+    descending: bool,
+}
+
+impl ServerBrowser {
+    pub fn new(open: bool) -> Self {
+        Self {
+            open,
+            ..Default::default()
+        }
+    }
+
+    fn refresh(&mut self, lobby_address: String) {
+        debug!("Fetching servers from lobby: {lobby_address}");
+
+        self.servers = Some(Promise::spawn_async(async move {
+            fetch_servers(&lobby_address, LOBBY_TIMEOUT).await
+        }));
+        self.pings.clear();
+    }
+
+    fn ping(
+        pings: &mut HashMap<SocketAddr, Promise<Option<Duration>>>,
+        address: SocketAddr,
+    ) -> Option<Duration> {
+        pings
+            .entry(address)
+            .or_insert_with(|| {
+                Promise::spawn_async(async move {
+                    probe(&address.to_string(), PING_TIMEOUT)
+                        .await
+                        .ok()
+                        .map(|report| report.round_trip)
+                })
+            })
+            .ready()
+            .copied()
+            .flatten()
+    }
+}
+
+impl GuiRenderer {
+    pub(super) fn draw_server_browser(&mut self, ctx: &egui::Context) {
+        if !self.server_browser.open {
+            return;
+        }
+
+        let lobby_address = || {
+            Promise::spawn_async(self.world.config().read())
+                .block_and_take()
+                .networking
+                .lobby_address
+                .clone()
+        };
+
+        if self.server_browser.servers.is_none() {
+            self.server_browser.refresh(lobby_address());
+        }
+
+        let mut open = true;
+        let mut refresh = false;
+        let mut join = None;
+
+        egui::Window::new("Server Browser")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([480.0, 320.0])
+            .show(ctx, |ui| {
+                refresh = ui.button("Refresh").clicked();
+                ui.separator();
+
+                join = self.draw_server_list(ui);
+            });
+
+        if refresh {
+            self.server_browser.refresh(lobby_address());
+        }
+
+        if let Some(address) = join {
+            info!("Joining from server browser: {address}");
+            open = false;
+
+            if let Err(error) = self
+                .world
+                .client_game_channel()
+                .send(ClientGameMessage::JoinServer(address.to_string()))
+            {
+                error!("Failed to send join server: {error}");
+            }
+        }
+
+        self.server_browser.open = open;
+    }
+
+    /// Returns the server clicked to join
+    fn draw_server_list(&mut self, ui: &mut egui::Ui) -> Option<SocketAddr> {
+        let ServerBrowser {
+            servers,
+            pings,
+            sort,
+            descending,
+            ..
+        } = &mut self.server_browser;
+
+        let servers = match servers.as_ref().and_then(Promise::ready) {
+            None => {
+                ui.spinner();
+                return None;
+            }
+            Some(Err(error)) => {
+                ui.label(format!("Couldn't reach the lobby: {error}"));
+                return None;
+            }
+            Some(Ok(servers)) if servers.is_empty() => {
+                ui.label("No servers are listed");
+                return None;
+            }
+            Some(Ok(servers)) => servers,
+        };
+
+        let mut rows = servers
+            .iter()
+            .map(|server| BrowserRow {
+                server,
+                ping: ServerBrowser::ping(pings, server.address),
+            })
+            .collect::<Vec<_>>();
+        sort_rows(&mut rows, *sort, *descending);
+
+        let mut join = None;
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("server_browser_grid")
+                .striped(true)
+                .num_columns(ServerSort::ALL.len())
+                .show(ui, |ui| {
+                    for column in ServerSort::ALL {
+                        let arrow = match (*sort == column, *descending) {
+                            (false, _) => "",
+                            (true, false) => " ⏶",
+                            (true, true) => " ⏷",
+                        };
+
+                        if ui
+                            .selectable_label(*sort == column, format!("{}{arrow}", column.label()))
+                            .clicked()
+                        {
+                            *descending = *sort == column && !*descending;
+                            *sort = column;
+                        }
+                    }
+                    ui.end_row();
+
+                    for row in &rows {
+                        let name = if row.server.passworded {
+                            format!("🔒 {}", row.server.name)
+                        } else {
+                            row.server.name.clone()
+                        };
+
+                        if ui.button(name).clicked() {
+                            join = Some(row.server.address);
+                        }
+
+                        ui.label(row.server.map.as_deref().unwrap_or("?"));
+                        ui.label(format!("{}/{}", row.server.players, row.server.slots));
+                        ui.label(match row.ping {
+                            Some(ping) => format!("{:.0} ms", ping.as_secs_f32() * 1000.0),
+                            None => "?".to_string(),
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+
+        join
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn server(name: &str, players: u16) -> LobbyServer {
+        LobbyServer {
+            address: SocketAddr::from(([10, 0, 0, players as u8], 8190)),
+            name: name.to_string(),
+            map: None,
+            players,
+            bots: 0,
+            slots: 24,
+            passworded: false,
+            info: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn sort_by_column() {
+        let servers = [server("beta", 3), server("Alpha", 10), server("gamma", 5)];
+        let pings = [
+            Some(Duration::from_millis(80)),
+            None,
+            Some(Duration::from_millis(20)),
+        ];
+
+        let mut rows = servers
+            .iter()
+            .zip(pings)
+            .map(|(server, ping)| BrowserRow { server, ping })
+            .collect::<Vec<_>>();
+        let names = |rows: &[BrowserRow]| {
+            rows.iter()
+                .map(|row| row.server.name.clone())
+                .collect::<Vec<_>>()
+        };
+
+        sort_rows(&mut rows, ServerSort::Name, false);
+        assert_eq!(names(&rows), ["Alpha", "beta", "gamma"]);
+
+        sort_rows(&mut rows, ServerSort::Players, true);
+        assert_eq!(names(&rows), ["Alpha", "gamma", "beta"]);
+
+        sort_rows(&mut rows, ServerSort::Ping, false);
+        assert_eq!(names(&rows), ["gamma", "beta", "Alpha"]);
+    }
+}
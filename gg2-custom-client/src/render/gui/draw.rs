@@ -1,7 +1,7 @@
 use poll_promise::Promise;
 
 use super::GuiRenderer;
-use crate::prelude::*;
+use crate::{config::ClientConfigSettings, prelude::*};
 
 const DEBUG_MENU_TRANSPARENCY: f32 = 0.75;
 const DEBUG_MENU_TRANSPARENCY_U8: u8 = (DEBUG_MENU_TRANSPARENCY * u8::MAX as f32) as u8;
@@ -16,23 +16,373 @@ impl GuiRenderer {
             gamemode_state.render_hud(ctx, self.world);
         }
 
+        self.draw_uber_meter(ctx);
+        self.draw_notice(ctx);
+
+        let (connection_state, latency, reconnect) = {
+            let network_client =
+                Promise::spawn_async(self.world.network_client().read()).block_and_take();
+
+            (
+                network_client.connection_state.clone(),
+                network_client.latency.clone(),
+                network_client.reconnect.clone(),
+            )
+        };
+
+        let reconnect_attempts = Promise::spawn_async(self.world.config().read())
+            .block_and_take()
+            .networking
+            .reconnect_attempts;
+
+        match connection_state {
+            NetworkingState::Disconnected(Some(reason)) => {
+                self.draw_disconnected(ctx, reason, &reconnect, reconnect_attempts);
+            }
+            NetworkingState::AttemptingConnection if reconnect.attempt > 0 => {
+                Self::draw_reconnecting(ctx, &reconnect, reconnect_attempts);
+            }
+            _ => (),
+        }
+
+        self.draw_password_prompt(ctx);
+        self.draw_server_browser(ctx);
+        self.draw_settings(ctx);
+
+        let round_over = Promise::spawn_async(self.world.round_state().read())
+            .block_and_take()
+            .round_over
+            .clone();
+
+        if let Some(round_over) = round_over {
+            Self::draw_round_over(ctx, &round_over);
+        }
+
+        self.draw_scoreboard(ctx, &latency);
+
         let debug_ui = Promise::spawn_async(self.world.config().read())
             .block_and_take()
             .debug
             .gui;
 
         if debug_ui {
-            self.draw_debug(ctx);
+            self.draw_debug(ctx, &latency);
+        }
+    }
+
+    fn draw_disconnected(
+        &mut self,
+        ctx: &egui::Context,
+        reason: DisconnectReason,
+        reconnect: &Reconnect,
+        reconnect_attempts: u32,
+    ) {
+        egui::Window::new("Disconnected")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(reason.to_string());
+
+                if reason.is_retryable() {
+                    if let Some(next_attempt) = reconnect.next_attempt {
+                        let wait =
+                            next_attempt.saturating_duration_since(tokio::time::Instant::now());
+
+                        ui.label(format!(
+                            "Reconnecting in {:.0}s (attempt {} of {reconnect_attempts})",
+                            wait.as_secs_f32().ceil(),
+                            reconnect.attempt + 1,
+                        ));
+                    } else if reconnect.attempt >= reconnect_attempts {
+                        ui.label(format!("Gave up after {} attempts", reconnect.attempt));
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("Reconnect").clicked() {
+                        Promise::spawn_async(self.world.network_client().write())
+                            .block_and_take()
+                            .connection_state = NetworkingState::Disconnected(None);
+                    }
+
+                    if ui.button("Server Browser").clicked() {
+                        self.server_browser.open = true;
+                    }
+
+                    if ui.button("Settings").clicked() {
+                        self.settings_open = true;
+                    }
+
+                    if ui.button("Exit Game").clicked() {
+                        self.world
+                            .render_channel()
+                            .send(RenderMessage::ExitNextFrame)
+                            .expect("Failed to send exit message to render thread");
+                    }
+                });
+            });
+    }
+
+    fn draw_notice(&self, ctx: &egui::Context) {
+        let message = {
+            let mut notice = Promise::spawn_async(self.world.notice().write()).block_and_take();
+
+            match notice.as_ref() {
+                Some(notice) if !notice.expired() => notice.message.clone(),
+                Some(_) => {
+                    *notice = None;
+                    return;
+                }
+                None => return,
+            }
+        };
+
+        egui::Window::new("Notice")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 16.0])
+            .show(ctx, |ui| {
+                ui.label(message);
+            });
+    }
+
+    fn draw_reconnecting(ctx: &egui::Context, reconnect: &Reconnect, reconnect_attempts: u32) {
+        egui::Window::new("Reconnecting")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Reconnecting (attempt {} of {reconnect_attempts})",
+                    reconnect.attempt
+                ));
+            });
+    }
+
+    fn draw_password_prompt(&self, ctx: &egui::Context) {
+        let mut network_client =
+            Promise::spawn_async(self.world.network_client().write()).block_and_take();
+
+        let Some(prompt) = network_client.password_prompt.as_mut() else {
+            return;
+        };
+
+        let mut cancel = false;
+
+        egui::Window::new("Password Required")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if prompt.wrong {
+                    ui.colored_label(egui::Color32::RED, "Wrong password");
+                }
+
+                let response = ui.add(egui::TextEdit::singleline(&mut prompt.input).password(true));
+                response.request_focus();
+
+                ui.checkbox(&mut prompt.remember, "Remember for this server");
+
+                ui.horizontal(|ui| {
+                    if ui.button("Join").clicked()
+                        || (response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)))
+                    {
+                        prompt.submitted = true;
+                    }
+
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if cancel {
+            network_client.disconnect(DisconnectReason::Left);
         }
     }
 
-    fn draw_debug(&mut self, ctx: &egui::Context) {
+    fn draw_uber_meter(&self, ctx: &egui::Context) {
+        let (uber_charge, ubered) = {
+            let players = Promise::spawn_async(self.world.client_players().read()).block_and_take();
+
+            match players.get_client() {
+                Ok(player) if player.class == ClassGeneric::Medic => {
+                    (player.uber_charge, player.ubered)
+                }
+                _ => return,
+            }
+        };
+
+        let text = if ubered {
+            "UBER ACTIVE".to_owned()
+        } else if uber_charge >= 1.0 {
+            "UBER READY".to_owned()
+        } else {
+            format!("UBER: {:.0}%", uber_charge * 100.0)
+        };
+
+        egui::Window::new("Uber Meter")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-16.0, -16.0])
+            .show(ctx, |ui| {
+                ui.add(egui::ProgressBar::new(uber_charge).text(text));
+            });
+    }
+
+    fn draw_round_over(ctx: &egui::Context, round_over: &RoundOver) {
+        let banner = match round_over.winners {
+            Team::Red => "RED WINS!",
+            Team::Blu => "BLU WINS!",
+            Team::Spectator => "STALEMATE",
+        };
+
+        egui::Window::new("Round Over")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 64.0])
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.heading(banner);
+                    ui.label(format!(
+                        "Next map: {} in {}s",
+                        round_over.next_map,
+                        round_over.map_change.as_secs_f32().ceil()
+                    ));
+                });
+            });
+    }
+
+    fn draw_scoreboard(&self, ctx: &egui::Context, latency: &Latency) {
+        let world = self.world;
+        let show_scores = Promise::spawn_async(async move {
+            let show_scores_bind = &world.config().read().await.controls.show_scores;
+
+            world
+                .input_state()
+                .read()
+                .await
+                .poll_button_bind(show_scores_bind, world)
+                .await
+                .unwrap_or_default()
+                .is_pressed()
+        })
+        .block_and_take();
+
+        if !show_scores {
+            return;
+        }
+
+        let players = Promise::spawn_async(world.client_players().read()).block_and_take();
+        let client_id = players.get_client_id().ok().map(usize::from);
+
+        egui::Window::new("Scoreboard")
+            .title_bar(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                for team in [Team::Red, Team::Blu] {
+                    ui.heading(team.to_string());
+
+                    egui::Grid::new(team).striped(true).show(ui, |ui| {
+                        ui.strong("Name");
+                        ui.strong("Class");
+                        ui.strong("Ping");
+                        ui.end_row();
+
+                        let team_players = players
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, player)| player.team == team);
+
+                        for (id, player) in team_players {
+                            ui.label(player.name.to_string());
+                            ui.label(player.class.to_string());
+                            // Servers only let a client measure its own latency
+                            if Some(id) == client_id {
+                                ui.label(latency.to_string());
+                            } else {
+                                ui.label("-");
+                            }
+                            ui.end_row();
+                        }
+                    });
+                }
+            });
+    }
+
+    fn draw_settings(&mut self, ctx: &egui::Context) {
+        let world = self.world;
+        let menu_pressed = Promise::spawn_async(async move {
+            let menu_bind = &world.config().read().await.controls.menu;
+
+            world
+                .input_state()
+                .read()
+                .await
+                .poll_button_bind(menu_bind, world)
+                .await
+                .unwrap_or_default()
+                .is_pressed()
+        })
+        .block_and_take();
+
+        if menu_pressed && !self.menu_pressed_last_frame {
+            self.settings_open = !self.settings_open;
+        }
+
+        self.menu_pressed_last_frame = menu_pressed;
+
+        let mut open = self.settings_open;
+
+        egui::Window::new("Settings")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let mut config = Promise::spawn_async(world.config().write()).block_and_take();
+
+                if ui
+                    .checkbox(&mut config.settings.queue_jump, "Queue Jump")
+                    .changed()
+                {
+                    self.send_client_settings(&config.settings);
+
+                    if let Err(error) = config.save() {
+                        error!("Failed to save client config: {error}");
+                    }
+                }
+            });
+
+        self.settings_open = open;
+    }
+
+    /// Sends changed settings to the server, which is otherwise told when joining
+    fn send_client_settings(&self, settings: &ClientConfigSettings) {
+        let in_game = Promise::spawn_async(self.world.network_client().read())
+            .block_and_take()
+            .connection_state
+            == NetworkingState::InGame;
+
+        if in_game
+            && let Err(error) =
+                self.world
+                    .client_game_channel()
+                    .send(ClientGameMessage::SendClientMessage(
+                        ClientMessageGeneric::ClientSettings(settings.into()),
+                    ))
+        {
+            error!("Failed to send client settings: {error}");
+        }
+    }
+
+    fn draw_debug(&mut self, ctx: &egui::Context, latency: &Latency) {
         egui::CentralPanel::default()
             .frame(
                 egui::Frame::default()
                     .fill(egui::Color32::from_black_alpha(DEBUG_MENU_TRANSPARENCY_U8)),
             )
             .show(ctx, |ui| {
+                ui.collapsing("Network", |ui| Self::draw_debug_network(ui, latency));
                 ui.collapsing("Client Player", |ui| self.draw_debug_player(ui));
                 ui.collapsing("Config", |ui| self.draw_debug_config(ui));
 
@@ -45,6 +395,22 @@ impl GuiRenderer {
             });
     }
 
+    fn draw_debug_network(ui: &mut egui::Ui, latency: &Latency) {
+        let Some(round_trip) = latency.round_trip() else {
+            ui.label("Latency unknown");
+            return;
+        };
+
+        ui.label(format!(
+            "Round Trip: {:.1}ms",
+            round_trip.as_secs_f32() * 1000.0
+        ));
+        ui.label(format!(
+            "Jitter: {:.1}ms",
+            latency.jitter().as_secs_f32() * 1000.0
+        ));
+    }
+
     fn draw_debug_player(&mut self, ui: &mut egui::Ui) {
         {
             let players = Promise::spawn_async(self.world.client_players().read()).block_and_take();
@@ -58,6 +424,10 @@ impl GuiRenderer {
             ui.label(format!("Player Name: {}", player.name));
             ui.label(format!("Player Team: {}", player.team));
             ui.label(format!("Player Class: {}", player.class));
+
+            if !player.rewards.is_empty() {
+                ui.label(format!("Player Rewards: {}", player.rewards));
+            }
         }
 
         egui::containers::ComboBox::from_label("Select Player Team")
@@ -13,4 +13,6 @@ pub enum ClientGameMessage {
     GilrsEvent(gilrs::Event),
     /// Send client message to server
     SendClientMessage(ClientMessageGeneric),
+    /// Leave the current server for another
+    JoinServer(String),
 }
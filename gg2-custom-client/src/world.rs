@@ -14,8 +14,13 @@ pub struct ClientWorld {
     config: RwLock<ClientConfig>,
     executable_directory: PathBuf,
     map_info: RwLock<MapInfo>,
+    map_cache: MapCache,
     network_client: RwLock<NetworkClient>,
+    notice: RwLock<Option<Notice>>,
     players: RwLock<ClientPlayers>,
+    plugin_registry: RwLock<PluginRegistry>,
+    round_state: RwLock<RoundState>,
+    moving_platforms: RwLock<Vec<MovingPlatform>>,
     winit_input_state: RwLock<WinitInputState>,
     gilrs_input_state: RwLock<GilrsInputState>,
     input_state: RwLock<InputState>,
@@ -43,15 +48,20 @@ impl ClientWorld {
             camera: Camera::default().into(),
             client_cli_arguments,
             config: config.into(),
+            map_cache: MapCache::user(&executable_directory),
             executable_directory,
             map_info: MapInfo::default().into(),
             network_client: NetworkClient::default().into(),
+            notice: None.into(),
             players: ClientPlayers::default().into(),
+            plugin_registry: PluginRegistry::builtin().into(),
+            round_state: RoundState::default().into(),
+            moving_platforms: Vec::new().into(),
             winit_input_state: WinitInputState::default().into(),
             gilrs_input_state: GilrsInputState::default().into(),
             input_state: InputState::new(Arc::clone(&winit_input_device)).into(),
             winit_input_device,
-            // TODO: Auto dectect gamemode
+            // Replaced once a map is loaded
             gamemode_state: RwLock::new(Some(Box::new(CaptureTheFlagState::default()))),
             delta_tick: AtomicF32::new(GAME_LOOP_INTERVAL),
         }
@@ -63,6 +73,13 @@ impl ClientWorld {
         &self.network_client
     }
 
+    /// The message currently shown on screen
+    #[inline]
+    #[must_use]
+    pub fn notice(&self) -> &RwLock<Option<Notice>> {
+        &self.notice
+    }
+
     #[inline]
     #[must_use]
     pub fn render_channel(&self) -> &UnboundedSender<RenderMessage> {
@@ -86,6 +103,12 @@ impl ClientWorld {
         &self.executable_directory
     }
 
+    #[inline]
+    #[must_use]
+    pub fn map_cache(&self) -> &MapCache {
+        &self.map_cache
+    }
+
     #[inline]
     #[must_use]
     pub fn camera(&self) -> &RwLock<Camera> {
@@ -140,6 +163,12 @@ impl ClientWorld {
         &self.players
     }
 
+    #[inline]
+    #[must_use]
+    pub fn plugin_registry(&self) -> &RwLock<PluginRegistry> {
+        &self.plugin_registry
+    }
+
     #[inline]
     #[must_use]
     pub fn client_gamemode_state(&self) -> &RwLock<Option<Box<dyn ClientGamemodeState>>> {
@@ -153,6 +182,16 @@ impl World for ClientWorld {
         &self.players
     }
 
+    #[inline]
+    fn round_state(&self) -> &RwLock<RoundState> {
+        &self.round_state
+    }
+
+    #[inline]
+    fn moving_platforms(&self) -> &RwLock<Vec<MovingPlatform>> {
+        &self.moving_platforms
+    }
+
     fn delta_tick(&self) -> f32 {
         self.delta_tick.load(std::sync::atomic::Ordering::Relaxed)
     }
@@ -1,5 +1,6 @@
-use std::time::Duration;
+use std::{any::Any, time::Duration};
 
+use gg2_common::player::team::TeamSpawnable;
 use tokio::time::Instant;
 
 use crate::prelude::*;
@@ -7,7 +8,9 @@ use crate::prelude::*;
 pub const GAME_TPS: f32 = 60.0;
 pub const GAME_LOOP_INTERVAL: f32 = 1.0 / GAME_TPS;
 
+pub mod control_point;
 pub mod gamemode;
+pub mod round;
 pub mod world;
 
 pub struct CommonGame {
@@ -41,9 +44,79 @@ impl CommonGame {
             }))
             .await?;
 
+        let delta = self.world.delta_tick();
+
+        let mut players = self.world.players().write().await;
+        players.iter_mut().for_each(|player| player.tick(delta));
+
+        for moving_platform in self.world.moving_platforms().write().await.iter_mut() {
+            let mut riders: Vec<_> = players
+                .iter_mut()
+                .filter(|player| moving_platform.carries(player))
+                .collect();
+            let start = moving_platform.transform.translation;
+
+            moving_platform.tick(delta);
+
+            let moved = moving_platform.transform.translation - start;
+            riders
+                .iter_mut()
+                .for_each(|player| player.transform.translation += moved);
+
+            players
+                .iter_mut()
+                .for_each(|player| moving_platform.collide(player));
+        }
+
+        drop(players);
+
+        self.world
+            .round_state()
+            .write()
+            .await
+            .tick(Duration::from_secs_f32(delta));
+
         Ok(())
     }
 
+    /// Runs a function on the gamemode state when it's a `T`
+    async fn with_gamemode_state<T: GamemodeState>(
+        &self,
+        function: impl FnOnce(&mut T) + Send + 'static,
+    ) -> Result<(), CommonError> {
+        self.world
+            .with_gamemode_state_mut(Box::new(|gamemode_state| {
+                Box::pin(async move {
+                    let gamemode_state = gamemode_state
+                        .and_then(|state| (state as &mut dyn Any).downcast_mut::<T>())
+                        .ok_or(CommonError::GamemodeMismatch)?;
+
+                    function(gamemode_state);
+
+                    Ok(())
+                })
+            }))
+            .await
+    }
+
+    /// Runs a function on the gamemode's control points
+    async fn with_control_points(
+        &self,
+        function: impl FnOnce(&mut [ControlPointState]) -> Result<(), CommonError> + Send + 'static,
+    ) -> Result<(), CommonError> {
+        self.world
+            .with_gamemode_state_mut(Box::new(|gamemode_state| {
+                Box::pin(async move {
+                    let control_points = gamemode_state
+                        .and_then(GamemodeState::control_points_mut)
+                        .ok_or(CommonError::GamemodeMismatch)?;
+
+                    function(control_points)
+                })
+            }))
+            .await
+    }
+
     async fn update_tick_delta(&mut self) {
         let delta = self.last_tick.elapsed();
 
@@ -59,10 +132,23 @@ impl CommonGame {
         player_id: PlayerId,
     ) -> Result<(), CommonError> {
         match generic_message {
+            ClientMessageGeneric::ClientSettings(message) => {
+                debug!("Player {player_id}: {message:#?}");
+                self.world
+                    .players()
+                    .write()
+                    .await
+                    .get_mut(player_id)?
+                    .queue_jump = message.queue_jump;
+            }
+            ClientMessageGeneric::DownloadMap(message) => debug!("{message:#?}"),
             ClientMessageGeneric::Hello(message) => debug!("{message:#?}"),
             ClientMessageGeneric::InputState(message) => {
-                debug!("Player {player_id}: {message:#?}");
+                trace!("Player {player_id}: {message:#?}");
+                let mut players = self.world.players().write().await;
+                Self::apply_player_raw_input(players.get_mut(player_id)?, message.input);
             }
+            ClientMessageGeneric::Ping(message) => trace!("Player {player_id}: {message:#?}"),
             ClientMessageGeneric::PlayerChangeClass(message) => {
                 debug!("Player {player_id}: {message:#?}");
                 self.world.players().write().await.get_mut(player_id)?.class = message.class;
@@ -72,7 +158,16 @@ impl CommonGame {
                 self.world.players().write().await.get_mut(player_id)?.team = message.team;
             }
             ClientMessageGeneric::PlayerJoin(message) => debug!("{message:#?}"),
+            ClientMessageGeneric::PluginPacket(message) => {
+                debug!("Player {player_id}: {message:#?}");
+            }
             ClientMessageGeneric::ReserveSlot(message) => debug!("{message:#?}"),
+            ClientMessageGeneric::RewardChallengeResponse(_) => {
+                debug!("Player {player_id}: Answered reward challenge");
+            }
+            ClientMessageGeneric::RewardRequest(message) => {
+                debug!("Player {player_id}: {message:#?}");
+            }
         }
 
         Ok(())
@@ -84,14 +179,49 @@ impl CommonGame {
         generic_message: ServerMessageGeneric,
     ) -> Result<(), CommonError> {
         match generic_message {
-            ServerMessageGeneric::CaptureUpdate(message) => debug!("{message:#?}"),
+            ServerMessageGeneric::ArenaEndround(message) => {
+                info!("Arena round over, winners: {}", message.winners);
+                self.with_gamemode_state(move |arena: &mut ArenaState| {
+                    arena.end_round(message.winners);
+                })
+                .await?;
+            }
+            ServerMessageGeneric::ArenaRestart(_) | ServerMessageGeneric::ArenaStartround(_) => {
+                self.with_gamemode_state(ArenaState::start_round).await?;
+            }
+            ServerMessageGeneric::ArenaWaitForPlayers(_) => {
+                self.with_gamemode_state(ArenaState::wait_for_players)
+                    .await?;
+            }
+            ServerMessageGeneric::Balance(message) => self.event_balance(message).await?,
+            ServerMessageGeneric::BlueTeamCap(_) => self.event_team_cap(TeamSpawnable::Blu).await,
+            ServerMessageGeneric::BuildSentry(message) => {
+                self.event_build_sentry(message).await?;
+            }
+            ServerMessageGeneric::CaptureUpdate(message) => {
+                self.event_capture_update(message).await;
+            }
             ServerMessageGeneric::ChatBubble(message) => debug!("{message:#?}"),
+            ServerMessageGeneric::CpCaptured(message) => self.event_cp_captured(message).await?,
+            ServerMessageGeneric::DestroySentry(message) => {
+                self.event_destroy_sentry(message).await?;
+            }
+            ServerMessageGeneric::GeneratorDestroy(message) => {
+                info!("{:?} generator destroyed", message.team);
+                self.with_gamemode_state(move |generator: &mut GeneratorState| {
+                    generator.destroy(message.team);
+                })
+                .await?;
+            }
             ServerMessageGeneric::DropIntel(message) => debug!("{message:#?}"),
             ServerMessageGeneric::GrabIntel(message) => debug!("{message:#?}"),
-            ServerMessageGeneric::FullUpdate(message) => debug!("{message:#?}"),
+            ServerMessageGeneric::FullUpdate(message) => {
+                self.event_full_update(message).await;
+            }
             ServerMessageGeneric::InputState(message) => {
                 self.event_input_state(message).await?;
             }
+            ServerMessageGeneric::MapEnd(message) => self.event_map_end(message).await,
             ServerMessageGeneric::MessageString(message) => {
                 info!("Server Message: {:?}", message.message);
             }
@@ -116,8 +246,33 @@ impl CommonGame {
             ServerMessageGeneric::QuickUpdate(message) => {
                 self.event_quick_update(message).await?;
             }
+            ServerMessageGeneric::RedTeamCap(_) => self.event_team_cap(TeamSpawnable::Red).await,
             ServerMessageGeneric::ReturnIntel(message) => debug!("{message:#?}"),
+            ServerMessageGeneric::RewardUpdate(message) => {
+                self.event_reward_update(message).await?;
+            }
             ServerMessageGeneric::ScoreIntel(message) => debug!("{message:#?}"),
+            ServerMessageGeneric::SentryPosition(message) => {
+                self.event_sentry_position(message).await?;
+            }
+            ServerMessageGeneric::ToggleZoom(message) => {
+                self.event_toggle_zoom(message).await?;
+            }
+            ServerMessageGeneric::Uber(message) => self.event_uber(message).await?,
+            ServerMessageGeneric::UberCharged(message) => {
+                self.event_uber_charged(message).await?;
+            }
+            ServerMessageGeneric::UnlockCp(_) => {
+                debug!("Control points unlocked");
+                self.with_control_points(|control_points| {
+                    for control_point in control_points {
+                        control_point.locked = false;
+                    }
+
+                    Ok(())
+                })
+                .await?;
+            }
             ServerMessageGeneric::WeaponFire(message) => {
                 self.event_weapon_fire(message).await?;
             }
@@ -127,6 +282,221 @@ impl CommonGame {
         Ok(())
     }
 
+    async fn event_balance(&self, message: ServerBalance) -> Result<(), CommonError> {
+        let Some(player_id) = message.player_id else {
+            info!("Teams are about to be balanced");
+            return Ok(());
+        };
+
+        let mut players = self.world.players().write().await;
+        let player = players.get_mut(player_id)?;
+
+        let team = match player.team {
+            Team::Red => Team::Blu,
+            Team::Blu => Team::Red,
+            Team::Spectator => {
+                warn!("Spectator {:?} can't be balanced", player.name);
+                return Ok(());
+            }
+        };
+
+        info!(
+            "Player {:?} balanced: {} => {team}",
+            player.name, player.team
+        );
+
+        player.team = team;
+
+        // The sentry switches sides with its owner
+        if let Some(sentry) = &mut player.sentry {
+            sentry.team = team;
+        }
+
+        Ok(())
+    }
+
+    async fn event_build_sentry(&self, message: ServerBuildSentry) -> Result<(), CommonError> {
+        debug!("{message:#?}");
+
+        let mut players = self.world.players().write().await;
+        let player = players.get_mut(message.player_id)?;
+
+        player.sentry = Some(Sentry::new(
+            player.team,
+            message.position,
+            message.looking_left,
+        ));
+
+        Ok(())
+    }
+
+    async fn event_capture_update(&self, message: ServerCaptureUpdate) {
+        debug!("{message:#?}");
+
+        self.world.round_state().write().await.captures = message.captures;
+    }
+
+    async fn event_cp_captured(&self, message: ServerCpCaptured) -> Result<(), CommonError> {
+        info!(
+            "Control point {} captured by {:?}",
+            message.control_point, message.team
+        );
+
+        self.with_control_points(move |control_points| {
+            control_points
+                .get_mut(usize::from(message.control_point))
+                .ok_or(CommonError::ControlPointLookup(message.control_point))?
+                .capture(message.team);
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn event_destroy_sentry(&self, message: ServerDestroySentry) -> Result<(), CommonError> {
+        debug!("{message:#?}");
+
+        let mut players = self.world.players().write().await;
+        players.get_mut(message.player_id)?.sentry = None;
+
+        Ok(())
+    }
+
+    async fn event_full_update(&self, message: ServerFullUpdate) {
+        debug!("{message:#?}");
+
+        for (player, info) in self
+            .world
+            .players()
+            .write()
+            .await
+            .iter_mut()
+            .zip(&message.player_info)
+        {
+            player.set_ubered(info.invulnerability);
+            player.rewards = Rewards::from(info.rewards.as_str());
+            player.queue_jump = info.queue_jump;
+
+            let Some((_, _, additional_info)) = &info.character else {
+                continue;
+            };
+
+            // Full updates don't repeat classes, the server sends each
+            // player's class change ahead of the update
+            let class = player.class;
+
+            if let Err(error) = player.apply_additional_info(class, additional_info) {
+                warn!(
+                    "Skipping {:?}'s {class} state from full update: {error}",
+                    player.name
+                );
+            }
+        }
+
+        self.apply_moving_platforms(message.moving_platforms).await;
+        self.world.round_state().write().await.captures = message.captures;
+    }
+
+    async fn event_map_end(&self, message: ServerMapEnd) {
+        info!(
+            "Round over, winners: {}, next map: {}",
+            message.winners, message.next_map
+        );
+
+        self.world
+            .round_state()
+            .write()
+            .await
+            .end_round(message.winners, message.next_map);
+    }
+
+    async fn event_reward_update(&self, message: ServerRewardUpdate) -> Result<(), CommonError> {
+        debug!("{message:#?}");
+
+        self.world
+            .players()
+            .write()
+            .await
+            .get_mut(message.player_id)?
+            .rewards = Rewards::from(message.rewards.as_str());
+
+        Ok(())
+    }
+
+    async fn event_sentry_position(
+        &self,
+        message: ServerSentryPosition,
+    ) -> Result<(), CommonError> {
+        trace!("{message:#?}");
+
+        let mut players = self.world.players().write().await;
+
+        if let Some(sentry) = &mut players.get_mut(message.player_id)?.sentry {
+            sentry.transform.translation.x = message.position.x;
+            sentry.transform.translation.y = message.position.y;
+        } else {
+            warn!("Player {} has no sentry to move", message.player_id);
+        }
+
+        Ok(())
+    }
+
+    async fn event_team_cap(&self, team: TeamSpawnable) {
+        let mut round_state = self.world.round_state().write().await;
+        round_state.capture(team);
+
+        debug!("Team {team:?} captured: {:?}", round_state.captures);
+    }
+
+    async fn event_toggle_zoom(&self, message: ServerToggleZoom) -> Result<(), CommonError> {
+        let mut players = self.world.players().write().await;
+        let player = players.get_mut(message.player_id)?;
+
+        match &mut player.class_state {
+            ClassState::Sniper { zoomed, .. } => *zoomed = !*zoomed,
+            _ if player.class == ClassGeneric::Sniper => {
+                player.class_state = ClassState::Sniper {
+                    charge: 0,
+                    zoomed: true,
+                };
+            }
+            _ => {
+                warn!("Player {:?} zoomed as {}", player.name, player.class);
+                return Ok(());
+            }
+        }
+
+        debug!("Player {:?} toggled zoom", player.name);
+
+        Ok(())
+    }
+
+    async fn event_uber(&self, message: ServerUber) -> Result<(), CommonError> {
+        let mut players = self.world.players().write().await;
+        let player = players.get_mut(message.player_id)?;
+
+        debug!("Player {:?} deployed uber", player.name);
+
+        player.set_ubered(true);
+
+        Ok(())
+    }
+
+    async fn event_uber_charged(&self, message: ServerUberCharged) -> Result<(), CommonError> {
+        let mut players = self.world.players().write().await;
+        let player = players.get_mut(message.player_id)?;
+
+        debug!("Player {:?} uber charged", player.name);
+
+        player.uber_charge = 1.0;
+        // Keeps the meter from following an older synced charge back down
+        player.class_state = ClassState::Medic {
+            uber_charge: MEDIC_UBER_MAX,
+        };
+
+        Ok(())
+    }
+
     async fn event_weapon_fire(&self, message: ServerWeaponFire) -> Result<(), CommonError> {
         debug!("{message:#?}");
 
@@ -243,9 +613,28 @@ impl CommonGame {
             Self::apply_player_raw_input(player, character_input);
         }
 
+        drop(players);
+        self.apply_moving_platforms(message.moving_platforms).await;
+
         Ok(())
     }
 
+    async fn apply_moving_platforms(&self, raw_platforms: Vec<RawMovingPlatform>) {
+        let mut moving_platforms = self.world.moving_platforms().write().await;
+
+        if moving_platforms.len() != raw_platforms.len() {
+            warn!(
+                "Server sent {} moving platforms, map has {}",
+                raw_platforms.len(),
+                moving_platforms.len()
+            );
+        }
+
+        for (moving_platform, raw) in moving_platforms.iter_mut().zip(raw_platforms) {
+            moving_platform.apply(raw);
+        }
+    }
+
     fn apply_player_raw_input(player: &mut Player, raw_input: RawInput) {
         player.input_state = raw_input;
 
@@ -0,0 +1,94 @@
+use gg2_common::player::team::TeamSpawnable;
+
+use crate::prelude::*;
+
+/// A control point as the round has left it
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ControlPointState {
+    pub position: Vec2,
+    /// The team that owns the point
+    /// Neutral if none
+    pub owner: Option<TeamSpawnable>,
+    /// Can't be captured until unlocked
+    pub locked: bool,
+    pub capturing_team: Option<TeamSpawnable>,
+    /// How far along the capture is
+    pub capturing: u16,
+}
+
+impl ControlPointState {
+    #[inline]
+    #[must_use]
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            position,
+            ..Default::default()
+        }
+    }
+
+    /// One for each of the map's control points, in the order they're sent
+    pub fn from_map(map_data: &MapData) -> Vec<Self> {
+        map_data
+            .control_points
+            .iter()
+            .copied()
+            .map(Self::new)
+            .collect()
+    }
+
+    /// Takes on the state sent in gamemode updates
+    pub fn apply(&mut self, raw: RawControlPoint) {
+        self.owner = raw.team;
+        self.capturing_team = raw.capturing_team;
+        self.capturing = raw.capturing;
+    }
+
+    pub fn to_raw(&self) -> RawControlPoint {
+        RawControlPoint {
+            team: self.owner,
+            capturing_team: self.capturing_team,
+            capturing: self.capturing,
+        }
+    }
+
+    pub fn capture(&mut self, team: TeamSpawnable) {
+        self.owner = Some(team);
+        self.capturing_team = None;
+        self.capturing = 0;
+    }
+
+    /// Returns the point to neutral, keeping its position
+    pub fn reset(&mut self) {
+        *self = Self::new(self.position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_clears_progress() {
+        let mut point = ControlPointState::new(Vec2::new(64.0, 32.0));
+        point.apply(RawControlPoint {
+            team: None,
+            capturing_team: Some(TeamSpawnable::Red),
+            capturing: 120,
+        });
+        assert_eq!(point.capturing_team, Some(TeamSpawnable::Red));
+
+        point.capture(TeamSpawnable::Red);
+        assert_eq!(
+            point.to_raw(),
+            RawControlPoint {
+                team: Some(TeamSpawnable::Red),
+                capturing_team: None,
+                capturing: 0,
+            }
+        );
+
+        point.locked = true;
+        point.reset();
+        assert_eq!(point, ControlPointState::new(Vec2::new(64.0, 32.0)));
+    }
+}
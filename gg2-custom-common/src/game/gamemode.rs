@@ -1,12 +1,33 @@
-use std::{pin::Pin, time::Duration};
+use std::{any::Any, pin::Pin, time::Duration};
+
+use gg2_common::player::team::TeamSpawnable;
 
 use crate::prelude::*;
 
-pub trait GamemodeState: Send + Sync {
+/// How long the result of an arena round shows before the next one
+pub const ARENA_END_DELAY: Duration = Duration::from_secs(5);
+/// The rate arena counts are sent in
+const ARENA_COUNT_FPS: f32 = 30.0;
+
+pub trait GamemodeState: Any + Send + Sync {
     fn tick<'a>(
         &'a mut self,
         world: &'a dyn World,
     ) -> Pin<Box<dyn Future<Output = Result<(), CommonError>> + 'a + Send>>;
+
+    /// The gamemode's control points, if it has any
+    fn control_points_mut(&mut self) -> Option<&mut [ControlPointState]> {
+        None
+    }
+}
+
+/// Unlocks every point once a lock timer has run out
+fn unlock_when_elapsed(control_points: &mut [ControlPointState], timer: Duration) {
+    if timer.is_zero() {
+        for control_point in control_points {
+            control_point.locked = false;
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -28,3 +49,329 @@ impl GamemodeState for CaptureTheFlagState {
         })
     }
 }
+
+/// Where an arena round is at
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ArenaPhase {
+    #[default]
+    WaitingForPlayers,
+    RoundActive,
+    RoundOver,
+}
+
+impl TryFrom<u8> for ArenaPhase {
+    type Error = NetworkError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::WaitingForPlayers),
+            1 => Ok(Self::RoundActive),
+            2 => Ok(Self::RoundOver),
+            _ => Err(NetworkError::PacketPayload),
+        }
+    }
+}
+
+impl From<ArenaPhase> for u8 {
+    fn from(value: ArenaPhase) -> Self {
+        match value {
+            ArenaPhase::WaitingForPlayers => 0,
+            ArenaPhase::RoundActive => 1,
+            ArenaPhase::RoundOver => 2,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ArenaState {
+    pub match_timer: HudMatchTimer,
+    pub phase: ArenaPhase,
+    pub red_wins: u8,
+    pub blu_wins: u8,
+    /// The team that won the last round
+    /// Spectator if it ended in a stalemate
+    pub winners: Team,
+    /// How long until the control point unlocks, as last sent by the server
+    pub control_point_unlock: Duration,
+    /// How long until the next round once one is over
+    pub end_count: Duration,
+    pub control_point: ControlPointState,
+}
+
+impl ArenaState {
+    pub fn wait_for_players(&mut self) {
+        self.phase = ArenaPhase::WaitingForPlayers;
+    }
+
+    pub fn start_round(&mut self) {
+        self.phase = ArenaPhase::RoundActive;
+        self.control_point.reset();
+        self.control_point.locked = true;
+    }
+
+    pub fn end_round(&mut self, winners: Team) {
+        self.phase = ArenaPhase::RoundOver;
+        self.winners = winners;
+        self.end_count = ARENA_END_DELAY;
+
+        match winners {
+            Team::Red => self.red_wins = self.red_wins.saturating_add(1),
+            Team::Blu => self.blu_wins = self.blu_wins.saturating_add(1),
+            Team::Spectator => {}
+        }
+    }
+
+    /// Takes on the state only sent with full updates
+    pub fn apply_full(&mut self, full: GamemodeHudArenaFull) -> Result<(), CommonError> {
+        self.phase = full.state.try_into()?;
+        self.winners = full
+            .winners
+            .try_into()
+            .map_err(|_| NetworkError::PacketPayload)?;
+        self.red_wins = full.red_wins;
+        self.blu_wins = full.blu_wins;
+        self.end_count = Duration::from_secs_f32(f32::from(full.end_count) / ARENA_COUNT_FPS);
+
+        Ok(())
+    }
+
+    pub fn to_full(&self) -> GamemodeHudArenaFull {
+        GamemodeHudArenaFull {
+            red_wins: self.red_wins,
+            blu_wins: self.blu_wins,
+            state: self.phase.into(),
+            winners: self.winners.into(),
+            end_count: (self.end_count.as_secs_f32() * ARENA_COUNT_FPS).round() as u16,
+        }
+    }
+}
+
+impl GamemodeState for ArenaState {
+    fn tick<'a>(
+        &'a mut self,
+        world: &'a dyn World,
+    ) -> Pin<Box<dyn Future<Output = Result<(), CommonError>> + 'a + Send>> {
+        Box::pin(async {
+            let delta = Duration::from_secs_f32(world.delta_tick());
+
+            match self.phase {
+                ArenaPhase::WaitingForPlayers => {}
+                ArenaPhase::RoundActive => {
+                    self.match_timer.left = self.match_timer.left.saturating_sub(delta);
+                    self.control_point_unlock = self.control_point_unlock.saturating_sub(delta);
+                    unlock_when_elapsed(
+                        std::slice::from_mut(&mut self.control_point),
+                        self.control_point_unlock,
+                    );
+                }
+                ArenaPhase::RoundOver => self.end_count = self.end_count.saturating_sub(delta),
+            }
+
+            Ok(())
+        })
+    }
+
+    fn control_points_mut(&mut self) -> Option<&mut [ControlPointState]> {
+        Some(std::slice::from_mut(&mut self.control_point))
+    }
+}
+
+/// Control point and attack/defence control point maps
+#[derive(Debug, Default)]
+pub struct ControlPointsState {
+    pub match_timer: HudMatchTimer,
+    /// How long until the points unlock
+    pub setup_timer: Duration,
+    pub control_points: Vec<ControlPointState>,
+}
+
+impl ControlPointsState {
+    #[inline]
+    #[must_use]
+    pub fn new(control_points: Vec<ControlPointState>) -> Self {
+        Self {
+            control_points,
+            ..Default::default()
+        }
+    }
+}
+
+impl GamemodeState for ControlPointsState {
+    fn tick<'a>(
+        &'a mut self,
+        world: &'a dyn World,
+    ) -> Pin<Box<dyn Future<Output = Result<(), CommonError>> + 'a + Send>> {
+        Box::pin(async {
+            let delta = Duration::from_secs_f32(world.delta_tick());
+
+            self.match_timer.left = self.match_timer.left.saturating_sub(delta);
+            self.setup_timer = self.setup_timer.saturating_sub(delta);
+            unlock_when_elapsed(&mut self.control_points, self.setup_timer);
+
+            Ok(())
+        })
+    }
+
+    fn control_points_mut(&mut self) -> Option<&mut [ControlPointState]> {
+        Some(&mut self.control_points)
+    }
+}
+
+/// King of the hill and dual king of the hill maps
+#[derive(Debug, Default)]
+pub struct KingOfTheHillState {
+    pub timer: HudKothTimer,
+    /// One point, or red's then blu's in dual KOTH
+    pub control_points: Vec<ControlPointState>,
+}
+
+impl KingOfTheHillState {
+    #[inline]
+    #[must_use]
+    pub fn new(control_points: Vec<ControlPointState>) -> Self {
+        Self {
+            control_points,
+            ..Default::default()
+        }
+    }
+}
+
+impl GamemodeState for KingOfTheHillState {
+    fn tick<'a>(
+        &'a mut self,
+        world: &'a dyn World,
+    ) -> Pin<Box<dyn Future<Output = Result<(), CommonError>> + 'a + Send>> {
+        Box::pin(async {
+            let delta = Duration::from_secs_f32(world.delta_tick());
+
+            self.timer.capture_unlock = self.timer.capture_unlock.saturating_sub(delta);
+            unlock_when_elapsed(&mut self.control_points, self.timer.capture_unlock);
+
+            Ok(())
+        })
+    }
+
+    fn control_points_mut(&mut self) -> Option<&mut [ControlPointState]> {
+        Some(&mut self.control_points)
+    }
+}
+
+/// Generator maps, won by destroying the other team's generator
+#[derive(Debug, Default)]
+pub struct GeneratorState {
+    pub match_timer: HudMatchTimer,
+    pub red_generator: RawGenerator,
+    pub blu_generator: RawGenerator,
+    /// The team whose generator was destroyed, ending the round
+    pub destroyed: Option<TeamSpawnable>,
+}
+
+impl GeneratorState {
+    pub fn generator(&self, team: TeamSpawnable) -> &RawGenerator {
+        match team {
+            TeamSpawnable::Red => &self.red_generator,
+            TeamSpawnable::Blu => &self.blu_generator,
+        }
+    }
+
+    pub fn destroy(&mut self, team: TeamSpawnable) {
+        let generator = match team {
+            TeamSpawnable::Red => &mut self.red_generator,
+            TeamSpawnable::Blu => &mut self.blu_generator,
+        };
+
+        generator.health = 0;
+        generator.shield_health = 0;
+        self.destroyed = Some(team);
+    }
+
+    /// The team that destroyed the other's generator
+    pub fn winners(&self) -> Option<TeamSpawnable> {
+        self.destroyed.map(TeamSpawnable::opponent)
+    }
+}
+
+impl GamemodeState for GeneratorState {
+    fn tick<'a>(
+        &'a mut self,
+        world: &'a dyn World,
+    ) -> Pin<Box<dyn Future<Output = Result<(), CommonError>> + 'a + Send>> {
+        Box::pin(async {
+            // The round is over once a generator is destroyed
+            if self.destroyed.is_none() {
+                self.match_timer.left = self
+                    .match_timer
+                    .left
+                    .saturating_sub(Duration::from_secs_f32(world.delta_tick()));
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arena_rounds() {
+        let mut arena = ArenaState {
+            control_point_unlock: Duration::from_secs(20),
+            ..Default::default()
+        };
+        arena.start_round();
+        assert_eq!(arena.phase, ArenaPhase::RoundActive);
+        assert_eq!(arena.control_point_unlock, Duration::from_secs(20));
+        assert!(arena.control_point.locked);
+
+        arena.end_round(Team::Blu);
+        arena.end_round(Team::Spectator);
+        assert_eq!(arena.phase, ArenaPhase::RoundOver);
+        assert_eq!((arena.red_wins, arena.blu_wins), (0, 1));
+        assert_eq!(arena.winners, Team::Spectator);
+    }
+
+    #[test]
+    fn arena_full_update() {
+        let mut arena = ArenaState::default();
+        arena.end_round(Team::Red);
+
+        let mut synced = ArenaState::default();
+        synced.apply_full(arena.to_full()).unwrap();
+        assert_eq!(synced.phase, ArenaPhase::RoundOver);
+        assert_eq!(synced.winners, Team::Red);
+        assert_eq!(synced.red_wins, 1);
+        assert_eq!(synced.end_count, ARENA_END_DELAY);
+
+        let invalid = GamemodeHudArenaFull {
+            state: 3,
+            ..arena.to_full()
+        };
+        assert!(synced.apply_full(invalid).is_err());
+    }
+
+    #[test]
+    fn generator_destroy() {
+        let mut generator = GeneratorState::default();
+        assert_eq!(generator.winners(), None);
+        assert_eq!(
+            generator.generator(TeamSpawnable::Blu).health,
+            GENERATOR_MAX_HEALTH
+        );
+
+        generator.destroy(TeamSpawnable::Blu);
+        assert_eq!(generator.winners(), Some(TeamSpawnable::Red));
+        assert_eq!(
+            *generator.generator(TeamSpawnable::Blu),
+            RawGenerator {
+                health: 0,
+                shield_health: 0,
+            }
+        );
+        assert_eq!(
+            generator.generator(TeamSpawnable::Red).health,
+            GENERATOR_MAX_HEALTH
+        );
+    }
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use gg2_common::player::team::{Captures, TeamSpawnable};
+
+use crate::prelude::*;
+
+/// How long the scoreboard shows between the round ending and the map changing
+pub const MAP_END_DELAY: Duration = Duration::from_secs(10);
+
+/// Progress of the current round shared by every gamemode
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RoundState {
+    pub captures: Captures,
+    /// Present once the round is over
+    pub round_over: Option<RoundOver>,
+}
+
+/// How the round ended
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundOver {
+    /// The team that won the round
+    /// Spectator if the round ended in a stalemate
+    pub winners: Team,
+    /// The map played after this one
+    pub next_map: GGStringShort,
+    /// How long until the server changes maps
+    pub map_change: Duration,
+}
+
+impl RoundState {
+    /// Counts one capture for a team
+    pub fn capture(&mut self, team: TeamSpawnable) {
+        let captures = match team {
+            TeamSpawnable::Red => &mut self.captures.red_captures,
+            TeamSpawnable::Blu => &mut self.captures.blu_captures,
+        };
+
+        *captures = captures.saturating_add(1);
+    }
+
+    pub fn end_round(&mut self, winners: Team, next_map: GGStringShort) {
+        self.round_over = Some(RoundOver {
+            winners,
+            next_map,
+            map_change: MAP_END_DELAY,
+        });
+    }
+
+    pub fn tick(&mut self, delta: Duration) {
+        if let Some(round_over) = &mut self.round_over {
+            round_over.map_change = round_over.map_change.saturating_sub(delta);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_saturates() {
+        let mut state = RoundState::default();
+        state.captures.blu_captures = u8::MAX - 1;
+
+        state.capture(TeamSpawnable::Red);
+        state.capture(TeamSpawnable::Blu);
+        state.capture(TeamSpawnable::Blu);
+
+        assert_eq!(state.captures.red_captures, 1);
+        assert_eq!(state.captures.blu_captures, u8::MAX);
+    }
+
+    #[test]
+    fn map_change_counts_down() {
+        let mut state = RoundState::default();
+        state.tick(Duration::from_secs(1));
+        assert_eq!(state.round_over, None);
+
+        state.end_round(Team::Blu, "ctf_2dfort".parse().unwrap());
+        state.tick(Duration::from_secs(4));
+        assert_eq!(
+            state
+                .round_over
+                .as_ref()
+                .map(|round_over| round_over.map_change),
+            Some(Duration::from_secs(6))
+        );
+
+        state.tick(MAP_END_DELAY);
+        assert_eq!(
+            state.round_over.map(|round_over| round_over.map_change),
+            Some(Duration::ZERO)
+        );
+    }
+}
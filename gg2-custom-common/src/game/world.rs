@@ -5,6 +5,12 @@ use crate::prelude::*;
 pub trait World: Send + Sync {
     fn players(&self) -> &RwLock<dyn Players>;
 
+    /// Captures and whether the round is over
+    fn round_state(&self) -> &RwLock<RoundState>;
+
+    /// The current map's moving platforms
+    fn moving_platforms(&self) -> &RwLock<Vec<MovingPlatform>>;
+
     /// The number of seconds since the last game tick.
     fn delta_tick(&self) -> f32;
 
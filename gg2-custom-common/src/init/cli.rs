@@ -1,4 +1,5 @@
 use clap::Args;
+use gg2_common::string::GGStringShort;
 
 #[derive(Debug, Args)]
 pub struct CommonCliJoinServer {
@@ -6,4 +7,8 @@ pub struct CommonCliJoinServer {
     /// Defaults to the client config's default server url
     #[arg(long)]
     pub server_url: Option<String>,
+    /// Sent if the server asks for a password
+    /// Takes priority over passwords saved in the client config
+    #[arg(long)]
+    pub password: Option<GGStringShort>,
 }
@@ -1,6 +1,8 @@
 pub mod game;
 pub mod init;
+pub mod moving_platform;
 pub mod player;
 pub mod prelude;
 pub mod resource;
+pub mod sentry;
 pub mod transform;
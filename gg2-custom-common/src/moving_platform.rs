@@ -0,0 +1,209 @@
+use crate::prelude::*;
+
+/// The size of a platform before the map scales it
+pub const MOVING_PLATFORM_SIZE: Vec2 = Vec2::new(64.0, 16.0);
+/// The rate map platform speeds are given in
+const MOVING_PLATFORM_FPS: f32 = 30.0;
+
+/// A platform travelling back and forth along a path from the map
+#[derive(Debug, Clone)]
+pub struct MovingPlatform {
+    pub transform: Transform,
+    pub path: MovingPlatformPath,
+    /// Heading back to the start of its path
+    pub returning: bool,
+}
+
+impl MovingPlatform {
+    pub fn new(path: MovingPlatformPath) -> Self {
+        Self {
+            transform: Transform {
+                translation: path.start.extend(0.0),
+                scale: MOVING_PLATFORM_SIZE * path.scale,
+                ..Default::default()
+            },
+            path,
+            returning: false,
+        }
+    }
+
+    /// One for each of the map's platforms, in the order they're sent
+    pub fn from_map(map_data: &MapData) -> Vec<Self> {
+        map_data
+            .moving_platforms
+            .iter()
+            .cloned()
+            .map(Self::new)
+            .collect()
+    }
+
+    /// The end of the path the platform is heading to
+    pub fn target(&self) -> Vec2 {
+        if self.returning {
+            self.path.start
+        } else {
+            self.path.end
+        }
+    }
+
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        f32::from(self.path.speed) * MOVING_PLATFORM_FPS
+    }
+
+    /// Pixels per second in the direction the platform is heading
+    pub fn velocity(&self) -> Vec2 {
+        (self.target() - self.transform.translation.xy()).normalize_or_zero() * self.speed()
+    }
+
+    /// Whether a point is on or inside the platform
+    pub fn contains(&self, point: Vec2) -> bool {
+        let half_size = self.transform.scale.abs() / 2.0;
+        let offset = (point - self.transform.translation.xy()).abs();
+
+        offset.cmple(half_size).all()
+    }
+
+    /// Whether a player is standing on the platform and moves with it
+    pub fn carries(&self, player: &Player) -> bool {
+        let feet =
+            player.transform.translation.xy() + Vec2::new(0.0, player.transform.scale.y / 2.0);
+
+        self.contains(feet)
+    }
+
+    /// Pushes a player overlapping the platform out the shortest way, stopping
+    /// them from moving into it
+    pub fn collide(&self, player: &mut Player) {
+        let offset = player.transform.translation.xy() - self.transform.translation.xy();
+        let overlap =
+            (self.transform.scale.abs() + player.transform.scale.abs()) / 2.0 - offset.abs();
+
+        if overlap.cmple(Vec2::ZERO).any() {
+            return;
+        }
+
+        let axis = if overlap.x < overlap.y { 0 } else { 1 };
+        let push = overlap[axis].copysign(offset[axis]);
+        player.transform.translation[axis] += push;
+
+        if player.velocity[axis] * push < 0.0 {
+            player.velocity[axis] = 0.0;
+        }
+    }
+
+    /// Takes on the state sent in updates
+    pub fn apply(&mut self, raw: RawMovingPlatform) {
+        self.transform.translation = raw.translation.extend(self.transform.translation.z);
+        self.returning = raw.returning;
+    }
+
+    pub fn to_raw(&self) -> RawMovingPlatform {
+        RawMovingPlatform {
+            translation: self.transform.translation.xy(),
+            returning: self.returning,
+        }
+    }
+
+    pub fn tick(&mut self, delta: f32) {
+        let target = self.target();
+
+        if self.speed() * delta >= target.distance(self.transform.translation.xy()) {
+            self.transform.translation = target.extend(self.transform.translation.z);
+            self.returning = !self.returning;
+        } else {
+            self.transform.translation += (self.velocity() * delta).extend(0.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::PLAYER_SCALE;
+
+    fn path() -> MovingPlatformPath {
+        MovingPlatformPath {
+            start: Vec2::new(0.0, 100.0),
+            end: Vec2::new(120.0, 100.0),
+            scale: Vec2::new(2.0, 1.0),
+            speed: 2,
+        }
+    }
+
+    #[test]
+    fn travels_back_and_forth() {
+        let mut platform = MovingPlatform::new(path());
+        assert_eq!(platform.velocity(), Vec2::new(60.0, 0.0));
+
+        platform.tick(1.0);
+        assert_eq!(platform.transform.translation.xy(), Vec2::new(60.0, 100.0));
+        assert!(!platform.returning);
+
+        platform.tick(1.5);
+        assert_eq!(platform.transform.translation.xy(), Vec2::new(120.0, 100.0));
+        assert!(platform.returning);
+        assert_eq!(platform.velocity(), Vec2::new(-60.0, 0.0));
+    }
+
+    #[test]
+    fn contains_scaled_bounds() {
+        let platform = MovingPlatform::new(path());
+
+        assert!(platform.contains(Vec2::new(64.0, 108.0)));
+        assert!(platform.contains(Vec2::new(-64.0, 92.0)));
+        assert!(!platform.contains(Vec2::new(65.0, 100.0)));
+        assert!(!platform.contains(Vec2::new(0.0, 109.0)));
+    }
+
+    #[test]
+    fn carries_players_standing_on_it() {
+        let platform = MovingPlatform::new(path());
+        let mut player = Player::from_name(GGStringShort::default());
+
+        player.transform.translation = Vec3::new(20.0, 100.0 - PLAYER_SCALE / 2.0, 0.0);
+        assert!(platform.carries(&player));
+
+        player.transform.translation.y -= 16.0;
+        assert!(!platform.carries(&player));
+    }
+
+    #[test]
+    fn pushes_players_out() {
+        let platform = MovingPlatform::new(path());
+        let mut player = Player::from_name(GGStringShort::default());
+
+        // Falling into the top lands on it
+        player.transform.translation = Vec3::new(20.0, 70.0, 0.0);
+        player.velocity = Vec2::new(10.0, 300.0);
+        platform.collide(&mut player);
+        assert_eq!(player.transform.translation.xy(), Vec2::new(20.0, 60.0));
+        assert_eq!(player.velocity, Vec2::new(10.0, 0.0));
+        assert!(platform.carries(&player));
+
+        // Walking into the side stops at the edge
+        player.transform.translation = Vec3::new(90.0, 100.0, 0.0);
+        player.velocity = Vec2::new(-50.0, 0.0);
+        platform.collide(&mut player);
+        assert_eq!(player.transform.translation.xy(), Vec2::new(96.0, 100.0));
+        assert_eq!(player.velocity, Vec2::ZERO);
+
+        // Clear of the platform stays put
+        player.velocity = Vec2::new(-50.0, 0.0);
+        platform.collide(&mut player);
+        assert_eq!(player.transform.translation.xy(), Vec2::new(96.0, 100.0));
+        assert_eq!(player.velocity, Vec2::new(-50.0, 0.0));
+    }
+
+    #[test]
+    fn raw_round_trip() {
+        let mut platform = MovingPlatform::new(path());
+        let raw = RawMovingPlatform {
+            translation: Vec2::new(40.0, 100.0),
+            returning: true,
+        };
+
+        platform.apply(raw.clone());
+        assert_eq!(platform.to_raw(), raw);
+    }
+}
@@ -1,6 +1,14 @@
+use std::time::Duration;
+
 use crate::prelude::*;
 
 pub const PLAYER_SCALE: f32 = 64.0;
+/// How long a full ubercharge lasts once deployed
+pub const UBER_DURATION: Duration = Duration::from_secs(8);
+/// How fast the uber meter catches up with the synced charge, per second
+const UBER_CATCH_UP_RATE: f32 = 0.5;
+/// The rate intel recharge is counted in
+const INTEL_RECHARGE_FPS: f32 = 30.0;
 
 #[derive(Debug, Default, Clone)]
 pub struct Player {
@@ -10,6 +18,22 @@ pub struct Player {
     pub input_state: RawInput,
     pub class: ClassGeneric,
     pub team: Team,
+    pub sentry: Option<Sentry>,
+    /// How full a medic's ubercharge is, from 0 to 1
+    pub uber_charge: f32,
+    /// Invulnerable from an ubercharge
+    pub ubered: bool,
+    /// How long a patient's ubercharge has left, since only medics sync a charge
+    pub uber_remaining: Duration,
+    pub rewards: Rewards,
+    /// Skips the respawn queue when the server allows it
+    pub queue_jump: bool,
+    /// Carrying the other team's intel
+    pub intel: bool,
+    /// How long until the player can pick up intel again
+    pub intel_recharge: Duration,
+    /// Only matches the player's class once a full update has arrived
+    pub class_state: ClassState,
 }
 
 impl Player {
@@ -23,6 +47,107 @@ impl Player {
             ..Default::default()
         }
     }
+
+    /// Takes on the character state sent with full updates, leaving the player
+    /// untouched when the state isn't valid for `class`
+    pub fn apply_additional_info(
+        &mut self,
+        class: ClassGeneric,
+        info: &RawAdditionalPlayerInfo,
+    ) -> Result<(), CommonError> {
+        self.class_state = ClassState::decode(class, &info.class_state)?;
+        self.intel = info.intel;
+
+        if let (
+            ClassState::Engineer {
+                sentry_health,
+                sentry_build_progress,
+                ..
+            },
+            Some(sentry),
+        ) = (&self.class_state, &mut self.sentry)
+        {
+            sentry.sync(*sentry_health, *sentry_build_progress);
+        }
+
+        self.intel_recharge =
+            Duration::from_secs_f32(f32::from(info.intel_recharge) / INTEL_RECHARGE_FPS);
+
+        Ok(())
+    }
+
+    pub fn additional_info(&self) -> RawAdditionalPlayerInfo {
+        let mut class_state = if self.class == ClassGeneric::Medic {
+            // The ubercharge is tracked on the player itself
+            ClassState::Medic {
+                uber_charge: (self.uber_charge * f32::from(MEDIC_UBER_MAX)).round() as u16,
+            }
+        } else if self.class_state.class() == self.class {
+            self.class_state.clone()
+        } else {
+            ClassState::new(self.class)
+        };
+
+        if let (
+            ClassState::Engineer {
+                sentry_health,
+                sentry_build_progress,
+                ..
+            },
+            Some(sentry),
+        ) = (&mut class_state, &self.sentry)
+        {
+            *sentry_health = sentry.health;
+            *sentry_build_progress = sentry.raw_build_progress();
+        }
+
+        RawAdditionalPlayerInfo {
+            intel: self.intel,
+            intel_recharge: (self.intel_recharge.as_secs_f32() * INTEL_RECHARGE_FPS).round() as u16,
+            class_state: class_state.encode(),
+        }
+    }
+
+    /// Starts or ends an ubercharge, giving a new one its full duration
+    pub fn set_ubered(&mut self, ubered: bool) {
+        if ubered && !self.ubered {
+            self.uber_remaining = UBER_DURATION;
+        }
+
+        self.ubered = ubered;
+    }
+
+    pub fn tick(&mut self, delta: f32) {
+        if self.ubered && self.class == ClassGeneric::Medic {
+            self.uber_charge -= delta / UBER_DURATION.as_secs_f32();
+
+            if self.uber_charge <= 0.0 {
+                self.uber_charge = 0.0;
+                self.ubered = false;
+
+                if let ClassState::Medic { uber_charge } = &mut self.class_state {
+                    *uber_charge = 0;
+                }
+            }
+        } else if self.ubered {
+            self.uber_remaining = self
+                .uber_remaining
+                .saturating_sub(Duration::from_secs_f32(delta));
+
+            if self.uber_remaining.is_zero() {
+                self.ubered = false;
+            }
+        } else if let ClassState::Medic { uber_charge } = self.class_state {
+            // Synced charge arrives in steps, so the meter fills towards it
+            let synced = f32::from(uber_charge) / f32::from(MEDIC_UBER_MAX);
+            let step = UBER_CATCH_UP_RATE * delta;
+            self.uber_charge += (synced - self.uber_charge).clamp(-step, step);
+        }
+
+        if let Some(sentry) = &mut self.sentry {
+            sentry.tick(delta);
+        }
+    }
 }
 
 pub trait Players: Send + Sync {
@@ -103,3 +228,136 @@ impl<'a, P: Players + ?Sized> PlayersIter<'a> for P {
             .flat_map(|(player, item)| item.map(|item| (player, item)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uber_drains() {
+        let mut medic = Player {
+            class: ClassGeneric::Medic,
+            uber_charge: 1.0,
+            ubered: true,
+            ..Default::default()
+        };
+        let mut patient = Player::default();
+        patient.set_ubered(true);
+
+        medic.tick(UBER_DURATION.as_secs_f32() / 2.0);
+        patient.tick(UBER_DURATION.as_secs_f32() / 2.0);
+        assert!(medic.ubered);
+        assert!(medic.uber_charge < 1.0);
+        assert!(patient.ubered);
+
+        // Still ubered in a later update, so the timer keeps running
+        patient.set_ubered(true);
+
+        medic.tick(UBER_DURATION.as_secs_f32());
+        patient.tick(UBER_DURATION.as_secs_f32());
+        assert!(!medic.ubered);
+        assert_eq!(medic.uber_charge, 0.0);
+        assert!(!patient.ubered);
+    }
+
+    #[test]
+    fn uber_meter_follows_synced_charge() {
+        let mut medic = Player {
+            class: ClassGeneric::Medic,
+            class_state: ClassState::Medic {
+                uber_charge: MEDIC_UBER_MAX / 2,
+            },
+            ..Default::default()
+        };
+
+        medic.tick(0.5);
+        assert_eq!(medic.uber_charge, 0.25);
+
+        medic.tick(1.0);
+        assert_eq!(medic.uber_charge, 0.5);
+
+        // A drained uber doesn't refill from the stale charge
+        medic.ubered = true;
+        medic.tick(UBER_DURATION.as_secs_f32());
+        medic.tick(1.0);
+        assert!(!medic.ubered);
+        assert_eq!(medic.uber_charge, 0.0);
+    }
+
+    #[test]
+    fn additional_info_round_trip() {
+        let medic = Player {
+            class: ClassGeneric::Medic,
+            uber_charge: 0.5,
+            intel: true,
+            intel_recharge: Duration::from_secs(2),
+            ..Default::default()
+        };
+
+        let mut synced = Player {
+            class: ClassGeneric::Medic,
+            ..Default::default()
+        };
+        synced
+            .apply_additional_info(ClassGeneric::Medic, &medic.additional_info())
+            .unwrap();
+        assert_eq!(synced.intel_recharge, Duration::from_secs(2));
+        assert!(synced.intel);
+        assert_eq!(synced.class_state, ClassState::Medic { uber_charge: 1000 });
+
+        // Stale state from another class isn't sent
+        let spy = Player {
+            class: ClassGeneric::Spy,
+            ..synced
+        };
+        assert_eq!(
+            spy.additional_info().class_state,
+            ClassState::new(ClassGeneric::Spy).encode()
+        );
+    }
+
+    #[test]
+    fn invalid_additional_info_is_ignored() {
+        let mut scout = Player {
+            class: ClassGeneric::Scout,
+            ..Default::default()
+        };
+        let info = RawAdditionalPlayerInfo {
+            intel: true,
+            intel_recharge: 0,
+            class_state: [2, 0, 0, 0, 0, 0],
+        };
+
+        assert!(scout.apply_additional_info(scout.class, &info).is_err());
+        assert!(!scout.intel);
+        assert_eq!(scout.class_state, ClassState::default());
+    }
+
+    #[test]
+    fn sentry_follows_full_update() {
+        let mut engineer = Player {
+            class: ClassGeneric::Engineer,
+            sentry: Some(Sentry::new(Team::Red, Vec2::ZERO, false)),
+            ..Default::default()
+        };
+        let info = RawAdditionalPlayerInfo {
+            intel: false,
+            intel_recharge: 0,
+            class_state: ClassState::Engineer {
+                nuts_n_bolts: 50,
+                sentry_health: 30,
+                sentry_build_progress: u8::MAX,
+            }
+            .encode(),
+        };
+
+        engineer
+            .apply_additional_info(engineer.class, &info)
+            .unwrap();
+
+        let sentry = engineer.sentry.as_ref().unwrap();
+        assert_eq!(sentry.health, 30);
+        assert!(sentry.is_built());
+        assert_eq!(engineer.additional_info(), info);
+    }
+}
@@ -1,11 +1,25 @@
 pub use dyn_future::DynFuture;
 pub use gg2_common::{
     error::CommonError,
+    game::{
+        control_point::RawControlPoint,
+        generator::{GENERATOR_MAX_HEALTH, GENERATOR_MAX_SHIELD_HEALTH, RawGenerator},
+        moving_platform::RawMovingPlatform,
+    },
     gamemode::Gamemode,
-    hud::{GamemodeHud, GamemodeHudArenaFull, HudMatchTimer},
-    map::{data::MapData, entity::MapEntity, io::error::MapIoError},
+    hud::{GamemodeHud, GamemodeHudArenaFull, HudKothTimer, HudMatchTimer},
+    map::{
+        data::{MapData, MovingPlatformPath},
+        entity::MapEntity,
+        io::{error::MapIoError, map_md5},
+    },
     networking::{AsPacketKind, PacketKind, error::NetworkError, message::*},
-    player::{KeyState, PlayerId, RawInput, class::ClassGeneric, team::Team},
+    player::{
+        KeyState, PlayerId, RawAdditionalPlayerInfo, RawInput,
+        class::{ClassGeneric, ClassState, MEDIC_UBER_MAX},
+        reward::Rewards,
+        team::Team,
+    },
     string::GGStringShort,
 };
 pub use glam::{Mat4, Quat, UVec2, Vec2, Vec2Swizzles, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
@@ -15,14 +29,21 @@ pub use tokio::sync::RwLock;
 pub use crate::{
     game::{
         CommonGame, GAME_LOOP_INTERVAL, GAME_TPS,
-        gamemode::{CaptureTheFlagState, GamemodeState},
+        control_point::ControlPointState,
+        gamemode::{
+            ArenaPhase, ArenaState, CaptureTheFlagState, ControlPointsState, GamemodeState,
+            GeneratorState, KingOfTheHillState,
+        },
+        round::{MAP_END_DELAY, RoundOver, RoundState},
         world::World,
     },
     init::cli::CommonCliJoinServer,
+    moving_platform::MovingPlatform,
     player::{Player, Players, PlayersIter},
     resource::{
         error::ResourceError,
         identifier::{ResourceId, ResourceType},
     },
+    sentry::Sentry,
     transform::Transform,
 };
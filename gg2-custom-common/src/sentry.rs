@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use crate::prelude::*;
+
+pub const SENTRY_SCALE: f32 = 32.0;
+pub const SENTRY_MAX_HEALTH: u8 = 100;
+/// How long a sentry takes to build after being placed
+pub const SENTRY_BUILD_TIME: Duration = Duration::from_secs(2);
+
+/// An engineer's sentry
+/// Owned by the player it's stored on
+#[derive(Debug, Clone)]
+pub struct Sentry {
+    pub team: Team,
+    pub transform: Transform,
+    /// From 0 when placed to 1 once built
+    pub build_progress: f32,
+    pub health: u8,
+}
+
+impl Sentry {
+    pub fn new(team: Team, position: Vec2, looking_left: bool) -> Self {
+        let scale_x = if looking_left {
+            -SENTRY_SCALE
+        } else {
+            SENTRY_SCALE
+        };
+
+        Self {
+            team,
+            transform: Transform {
+                translation: position.extend(0.0),
+                scale: Vec2::new(scale_x, SENTRY_SCALE),
+                ..Default::default()
+            },
+            build_progress: 0.0,
+            health: SENTRY_MAX_HEALTH,
+        }
+    }
+
+    #[inline]
+    pub fn is_built(&self) -> bool {
+        self.build_progress >= 1.0
+    }
+
+    /// The build progress as sent in an engineer's class state
+    pub fn raw_build_progress(&self) -> u8 {
+        (self.build_progress * f32::from(u8::MAX)).round() as u8
+    }
+
+    /// Takes on the health and build progress sent with full updates
+    pub fn sync(&mut self, health: u8, raw_build_progress: u8) {
+        self.health = health;
+        self.build_progress = f32::from(raw_build_progress) / f32::from(u8::MAX);
+    }
+
+    pub fn tick(&mut self, delta: f32) {
+        self.build_progress =
+            (self.build_progress + delta / SENTRY_BUILD_TIME.as_secs_f32()).min(1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_progress() {
+        let mut sentry = Sentry::new(Team::Red, Vec2::new(64.0, 32.0), true);
+        assert!(!sentry.is_built());
+        assert_eq!(sentry.transform.scale.x, -SENTRY_SCALE);
+
+        sentry.tick(SENTRY_BUILD_TIME.as_secs_f32() / 2.0);
+        assert!(!sentry.is_built());
+
+        sentry.tick(SENTRY_BUILD_TIME.as_secs_f32());
+        assert!(sentry.is_built());
+        assert_eq!(sentry.build_progress, 1.0);
+    }
+
+    #[test]
+    fn sync_from_full_update() {
+        let mut sentry = Sentry::new(Team::Blu, Vec2::ZERO, false);
+        sentry.sync(40, 51);
+        assert_eq!(sentry.health, 40);
+        assert_eq!(sentry.build_progress, 0.2);
+        assert_eq!(sentry.raw_build_progress(), 51);
+    }
+}
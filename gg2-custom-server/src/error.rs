@@ -0,0 +1,18 @@
+use std::path::PathBuf;
+
+use crate::prelude::*;
+
+/// All of the server's errors
+#[derive(Debug, thiserror::Error)]
+pub enum ServerError {
+    #[error("Common Error: {0}")]
+    Common(#[from] CommonError),
+    #[error("Network Error: {0}")]
+    Network(#[from] NetworkError),
+    #[error("Map Error: {0}")]
+    Map(#[from] MapIoError),
+    #[error("Failed to read map '{1}': {0}")]
+    MapRead(std::io::Error, PathBuf),
+    #[error("Map file name is invalid: {0}")]
+    MapName(PathBuf),
+}
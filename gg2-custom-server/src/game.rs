@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use crate::prelude::*;
+
+pub mod gamemode;
+
+pub struct ServerGame {
+    pub world: &'static ServerWorld,
+    pub game: CommonGame,
+}
+
+impl ServerGame {
+    pub fn new(world: &'static ServerWorld) -> Self {
+        Self {
+            world,
+            game: CommonGame::new(world),
+        }
+    }
+
+    pub async fn start_update(mut self) {
+        let mut interval = tokio::time::interval(Duration::from_secs_f32(GAME_LOOP_INTERVAL));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(error) = self.update().await {
+                error!("{error}");
+            }
+        }
+    }
+
+    async fn update(&mut self) -> Result<(), ServerError> {
+        self.game.pre_tick().await?;
+        self.update_network_server().await?;
+        self.game.tick().await?;
+        self.broadcast_updates().await?;
+
+        Ok(())
+    }
+
+    /// Sends every character's state to all players
+    async fn broadcast_updates(&self) -> Result<(), ServerError> {
+        let player_characters = self
+            .world
+            .server_players()
+            .read()
+            .await
+            .iter()
+            .map(ServerPlayers::character)
+            .collect::<Vec<_>>();
+
+        let inputs = player_characters
+            .iter()
+            .map(|character| character.as_ref().map(|(input, _)| input.clone()))
+            .collect();
+
+        let moving_platforms = self.moving_platforms().await;
+        let network_server = self.world.network_server().read().await;
+
+        network_server
+            .broadcast_message(ServerInputState { inputs }, self.world)
+            .await?;
+        network_server
+            .broadcast_message(
+                ServerQuickUpdate {
+                    player_characters,
+                    moving_platforms,
+                },
+                self.world,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn moving_platforms(&self) -> Vec<RawMovingPlatform> {
+        self.world
+            .moving_platforms()
+            .read()
+            .await
+            .iter()
+            .map(MovingPlatform::to_raw)
+            .collect()
+    }
+
+    pub async fn full_update(&self) -> ServerFullUpdate {
+        let players = self.world.server_players().read().await;
+        let dominations = vec![0; players.len().saturating_sub(1).into()];
+
+        let player_info = players
+            .iter()
+            .map(|player| PlayerUpdateInfo {
+                kills: 0,
+                deaths: 0,
+                captures: 0,
+                assists: 0,
+                destruction: 0,
+                stabs: 0,
+                healing: 0,
+                defenses: 0,
+                invulnerability: false,
+                bonus: 0,
+                points: 0,
+                queue_jump: player.queue_jump,
+                rewards: GGStringLong::default(),
+                dominations: dominations.clone(),
+                character: ServerPlayers::character(player)
+                    .map(|(input, player_info)| (input, player_info, player.additional_info())),
+            })
+            .collect();
+
+        ServerFullUpdate {
+            team_death_match_invulnerability_ticks: 0,
+            player_info,
+            moving_platforms: self.moving_platforms().await,
+            // TODO: Spawn intel
+            red_intel: Vec::new(),
+            blu_intel: Vec::new(),
+            capture_limit: 3,
+            captures: self.world.round_state().read().await.captures.clone(),
+            scout_limit: u8::MAX,
+            soldier_limit: u8::MAX,
+            sniper_limit: u8::MAX,
+            demoman_limit: u8::MAX,
+            medic_limit: u8::MAX,
+            engineer_limit: u8::MAX,
+            heavy_limit: u8::MAX,
+            spy_limit: u8::MAX,
+            pyro_limit: u8::MAX,
+            quote_limit: u8::MAX,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::prelude::*;
+
+pub trait ServerGamemodeState: GamemodeState {
+    fn serialize(&self, buffer: &mut Vec<u8>, kind: PacketKind) -> Result<(), CommonError>;
+}
+
+impl ServerGamemodeState for CaptureTheFlagState {
+    fn serialize(&self, buffer: &mut Vec<u8>, _kind: PacketKind) -> Result<(), CommonError> {
+        self.match_timer.serialize(buffer)
+    }
+}
+
+impl ServerGamemodeState for ArenaState {
+    fn serialize(&self, buffer: &mut Vec<u8>, kind: PacketKind) -> Result<(), CommonError> {
+        if kind == PacketKind::FullUpdate {
+            self.to_full().serialize(buffer)?;
+        }
+
+        self.match_timer.serialize(buffer)?;
+        buffer.write_duration_u16_sec(self.control_point_unlock);
+        // Round start, which the client ignores
+        buffer.write_u8(0);
+        self.control_point.to_raw().serialize(buffer)
+    }
+}
+
+impl ServerGamemodeState for ControlPointsState {
+    fn serialize(&self, buffer: &mut Vec<u8>, _kind: PacketKind) -> Result<(), CommonError> {
+        self.match_timer.serialize(buffer)?;
+        buffer.write_duration_u16_sec(self.setup_timer);
+
+        for control_point in &self.control_points {
+            control_point.to_raw().serialize(buffer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ServerGamemodeState for KingOfTheHillState {
+    fn serialize(&self, buffer: &mut Vec<u8>, _kind: PacketKind) -> Result<(), CommonError> {
+        self.timer.serialize(buffer)?;
+
+        for control_point in &self.control_points {
+            control_point.to_raw().serialize(buffer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ServerGamemodeState for GeneratorState {
+    fn serialize(&self, buffer: &mut Vec<u8>, _kind: PacketKind) -> Result<(), CommonError> {
+        self.match_timer.serialize(buffer)?;
+        self.blu_generator.serialize(buffer)?;
+        self.red_generator.serialize(buffer)
+    }
+}
@@ -0,0 +1,48 @@
+use std::sync::OnceLock;
+
+use crate::prelude::*;
+
+static WORLD: OnceLock<ServerWorld> = OnceLock::new();
+
+pub mod cli;
+
+pub struct App {
+    pub server_cli_arguments: ServerCliArguments,
+}
+
+impl App {
+    /// Initializes the server
+    pub fn new() -> Self {
+        env_logger::init();
+
+        let server_cli_arguments = cli::init();
+
+        Self {
+            server_cli_arguments,
+        }
+    }
+
+    async fn setup(server_cli_arguments: ServerCliArguments) -> Result<(), ServerError> {
+        let map_info = MapInfo::load(&server_cli_arguments.map).await?;
+        info!("Loaded map: {}", map_info.name);
+
+        let port = server_cli_arguments.port;
+        let world = WORLD.get_or_init(|| ServerWorld::new(server_cli_arguments, map_info));
+
+        let address = world.network_server().write().await.listen(port).await?;
+        info!("Listening on {address}");
+
+        ServerGame::new(world).start_update().await;
+
+        Ok(())
+    }
+
+    pub fn start(self) -> Result<(), ServerError> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .expect("Failed building the Runtime");
+
+        runtime.block_on(Self::setup(self.server_cli_arguments))
+    }
+}
@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use gg2_common::networking::DEFAULT_PORT;
+
+use crate::prelude::*;
+
+#[derive(Debug, Parser)]
+#[command(version, about, long_about = None)]
+pub struct ServerCliArguments {
+    /// The map file to host
+    /// The map's name is taken from the file name
+    pub map: PathBuf,
+    /// The port to listen for players on
+    #[arg(long, default_value_t = DEFAULT_PORT)]
+    pub port: u16,
+    /// The name shown to players
+    #[arg(long, default_value = "Rust Server")]
+    pub server_name: GGStringShort,
+    /// Players must send this password to join
+    #[arg(long)]
+    pub password: Option<GGStringShort>,
+    /// The most players allowed at once
+    #[arg(long, default_value_t = 24)]
+    pub max_players: u8,
+}
+
+pub fn init() -> ServerCliArguments {
+    ServerCliArguments::parse()
+}
@@ -0,0 +1,14 @@
+mod error;
+mod game;
+mod init;
+mod map;
+mod networking;
+mod player;
+mod prelude;
+mod world;
+
+use prelude::*;
+
+fn main() -> Result<(), ServerError> {
+    init::App::new().start()
+}
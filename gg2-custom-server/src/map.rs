@@ -0,0 +1,36 @@
+use std::path::Path;
+
+use crate::prelude::*;
+
+#[derive(Debug)]
+pub struct MapInfo {
+    pub name: GGStringShort,
+    /// Not present when the map is builtin
+    pub md5: Option<u128>,
+    pub data: MapData,
+    /// The map's PNG, sent to clients that download it
+    pub file: Vec<u8>,
+}
+
+impl MapInfo {
+    pub async fn load(path: &Path) -> Result<Self, ServerError> {
+        let name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| ServerError::MapName(path.to_path_buf()))?
+            .parse()?;
+
+        let map_buffer = tokio::fs::read(path)
+            .await
+            .map_err(|error| ServerError::MapRead(error, path.to_path_buf()))?;
+
+        let data = MapData::load_from_memory(&map_buffer)?;
+
+        Ok(Self {
+            name,
+            md5: Some(map_md5(&map_buffer)),
+            data,
+            file: map_buffer,
+        })
+    }
+}
@@ -0,0 +1,353 @@
+use std::{
+    collections::VecDeque,
+    fmt::Display,
+    net::{Ipv4Addr, SocketAddr},
+    sync::Arc,
+};
+
+use gg2_server::networking::message::ServerNetworkDeserialize;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{
+        TcpListener, TcpStream,
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    },
+    sync::{
+        Mutex,
+        mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
+    },
+    task::JoinHandle,
+};
+
+use crate::prelude::*;
+
+pub const MAX_PACKET_LENGTH: usize = 1024;
+
+#[derive(Debug)]
+pub struct SyncChannel<T> {
+    pub sender: UnboundedSender<T>,
+    pub receiver: UnboundedReceiver<T>,
+}
+
+impl<T> Default for SyncChannel<T> {
+    fn default() -> Self {
+        let (sender, receiver) = unbounded_channel();
+
+        Self { sender, receiver }
+    }
+}
+
+/// Identifies a connection for as long as the server runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConnectionId(u64);
+
+impl Display for ConnectionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Debug)]
+pub enum ServerNetworkEvent {
+    Connected(TcpStream, SocketAddr),
+    Disconnected(ConnectionId),
+    Error(NetworkError),
+}
+
+#[derive(Debug)]
+pub struct ClientConnection {
+    pub id: ConnectionId,
+    pub address: SocketAddr,
+    receive_task: JoinHandle<()>,
+    /// Finishes sending queued messages once `send_message` is dropped
+    _send_task: JoinHandle<()>,
+    send_message: UnboundedSender<Vec<u8>>,
+    receive_message: Arc<Mutex<VecDeque<u8>>>,
+    pub state: ClientConnectionState,
+    /// The name the slot was reserved with
+    pub player_name: Option<GGStringShort>,
+    /// Present once the client has joined
+    pub player_id: Option<PlayerId>,
+}
+
+impl ClientConnection {
+    /// Whether the connection has a reserved or joined player
+    #[inline]
+    pub fn has_slot(&self) -> bool {
+        self.player_name.is_some() || self.player_id.is_some()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct NetworkServer {
+    listen_task: Option<JoinHandle<()>>,
+    connections: Vec<ClientConnection>,
+    next_connection_id: u64,
+    pub network_events: SyncChannel<ServerNetworkEvent>,
+}
+
+impl NetworkServer {
+    /// Starts accepting connections on all interfaces
+    pub async fn listen(&mut self, port: u16) -> Result<SocketAddr, NetworkError> {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))
+            .await
+            .map_err(NetworkError::Listen)?;
+        let address = listener.local_addr().map_err(NetworkError::Listen)?;
+
+        if let Some(task) = self.listen_task.replace(tokio::spawn(listen_task(
+            listener,
+            self.network_events.sender.clone(),
+        ))) {
+            task.abort();
+        }
+
+        Ok(address)
+    }
+
+    /// Sets up send and receive tasks for a new connection
+    pub fn add_connection(&mut self, stream: TcpStream, address: SocketAddr) -> ConnectionId {
+        let id = ConnectionId(self.next_connection_id);
+        self.next_connection_id += 1;
+
+        let (read_socket, send_socket) = stream.into_split();
+        let (send_message, receive_message) = unbounded_channel();
+        let receive_queue = Arc::default();
+
+        self.connections.push(ClientConnection {
+            id,
+            address,
+            _send_task: tokio::spawn(send_task(
+                id,
+                receive_message,
+                send_socket,
+                self.network_events.sender.clone(),
+            )),
+            receive_task: tokio::spawn(receive_task(
+                id,
+                read_socket,
+                Arc::clone(&receive_queue),
+                self.network_events.sender.clone(),
+            )),
+            send_message,
+            receive_message: receive_queue,
+            state: ClientConnectionState::default(),
+            player_name: None,
+            player_id: None,
+        });
+
+        id
+    }
+
+    /// Forgets a connection, closing it once dropped
+    pub fn remove_connection(&mut self, id: ConnectionId) -> Option<ClientConnection> {
+        let index = self
+            .connections
+            .iter()
+            .position(|connection| connection.id == id)?;
+        Some(self.connections.remove(index))
+    }
+
+    #[inline]
+    pub fn connections(&self) -> &[ClientConnection] {
+        &self.connections
+    }
+
+    #[inline]
+    pub fn connections_mut(&mut self) -> &mut [ClientConnection] {
+        &mut self.connections
+    }
+
+    pub fn connection_ids(&self) -> Vec<ConnectionId> {
+        self.connections
+            .iter()
+            .map(|connection| connection.id)
+            .collect()
+    }
+
+    pub fn connection(&self, id: ConnectionId) -> Result<&ClientConnection, NetworkError> {
+        self.connections
+            .iter()
+            .find(|connection| connection.id == id)
+            .ok_or(NetworkError::ConnectionNotFound)
+    }
+
+    pub fn connection_mut(
+        &mut self,
+        id: ConnectionId,
+    ) -> Result<&mut ClientConnection, NetworkError> {
+        self.connections
+            .iter_mut()
+            .find(|connection| connection.id == id)
+            .ok_or(NetworkError::ConnectionNotFound)
+    }
+
+    /// Sends bytes as they are, outside of any message
+    pub fn send_raw(&self, id: ConnectionId, buffer: Vec<u8>) -> Result<(), NetworkError> {
+        trace!("Sending message to connection {id}.");
+        self.connection(id)?
+            .send_message
+            .send(buffer)
+            .map_err(|_| NetworkError::ChannelClosed)
+    }
+
+    pub async fn send_message<T: ServerNetworkSerialize + AsPacketKind>(
+        &self,
+        id: ConnectionId,
+        message: T,
+        context: &impl ServerNetworkSerializationContext,
+    ) -> Result<(), CommonError> {
+        let mut buffer = Vec::with_capacity(256);
+        buffer.push(message.as_packet_kind().into());
+        message.serialize(&mut buffer, context).await?;
+
+        Ok(self.send_raw(id, buffer)?)
+    }
+
+    /// Sends a message to every client that's in game
+    pub async fn broadcast_message<T: ServerNetworkSerialize + AsPacketKind>(
+        &self,
+        message: T,
+        context: &impl ServerNetworkSerializationContext,
+    ) -> Result<(), CommonError> {
+        let mut buffer = Vec::with_capacity(256);
+        buffer.push(message.as_packet_kind().into());
+        message.serialize(&mut buffer, context).await?;
+
+        for connection in &self.connections {
+            if connection.state == ClientConnectionState::InGame {
+                // A closed channel is handled by the disconnect event
+                let _ = connection.send_message.send(buffer.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the connection's next message once it has fully arrived
+    pub async fn pop_message<T: ServerNetworkDeserialize>(
+        &self,
+        id: ConnectionId,
+    ) -> Result<Option<T>, CommonError> {
+        let connection = self.connection(id)?;
+        let queue = &mut *connection.receive_message.lock().await;
+
+        if queue.is_empty() {
+            return Ok(None);
+        }
+
+        // Left in place until all of the message has arrived
+        let mut payload = MessageReader::new(queue.make_contiguous());
+
+        match T::deserialize(&mut payload, &connection.state) {
+            Ok(message) => {
+                let message_length = payload.consumed();
+                queue.drain(..message_length);
+                Ok(Some(message))
+            }
+            Err(CommonError::Network(NetworkError::UnexpectedEOF)) => Ok(None),
+            Err(error) => {
+                purge_queue(id, queue);
+                Err(error)
+            }
+        }
+    }
+}
+
+impl Drop for ClientConnection {
+    /// Stops receiving while letting queued messages finish sending
+    fn drop(&mut self) {
+        self.receive_task.abort();
+    }
+}
+
+/// Clears the message queue in the event something goes wrong
+fn purge_queue(id: ConnectionId, queue: &mut VecDeque<u8>) {
+    if !queue.is_empty() {
+        debug!("Purging queue of connection {id}...");
+
+        let data = Vec::from(std::mem::take(queue));
+        debug!("'{}'", data.escape_ascii());
+    }
+}
+
+// Accepts new connections
+async fn listen_task(
+    listener: TcpListener,
+    network_event_sender: UnboundedSender<ServerNetworkEvent>,
+) {
+    loop {
+        let event = match listener.accept().await {
+            Ok((stream, address)) => ServerNetworkEvent::Connected(stream, address),
+            Err(error) => ServerNetworkEvent::Error(NetworkError::Accept(error)),
+        };
+
+        if network_event_sender.send(event).is_err() {
+            break;
+        }
+    }
+}
+
+// Sends network packets to a client
+async fn send_task(
+    id: ConnectionId,
+    mut receive_message: UnboundedReceiver<Vec<u8>>,
+    mut send_socket: OwnedWriteHalf,
+    network_event_sender: UnboundedSender<ServerNetworkEvent>,
+) {
+    while let Some(message) = receive_message.recv().await {
+        trace!("Sending to {id}: {}", message.escape_ascii());
+
+        if send_socket.write_all(&message).await.is_err() {
+            let _ = network_event_sender.send(ServerNetworkEvent::Error(NetworkError::PacketSend));
+            break;
+        }
+    }
+
+    let _ = network_event_sender.send(ServerNetworkEvent::Disconnected(id));
+}
+
+// Receives data from a client and passes network packets
+async fn receive_task(
+    id: ConnectionId,
+    mut read_socket: OwnedReadHalf,
+    receive_messages: Arc<Mutex<VecDeque<u8>>>,
+    network_event_sender: UnboundedSender<ServerNetworkEvent>,
+) {
+    let mut buffer = [0; MAX_PACKET_LENGTH];
+
+    // Zero bytes means the client closed the connection
+    while let Ok(length @ 1..) = read_socket.read(&mut buffer).await {
+        trace!(
+            "Received {} bytes from {}: {}",
+            length,
+            id,
+            buffer[..length].escape_ascii()
+        );
+
+        receive_messages.lock().await.extend(&buffer[..length]);
+    }
+
+    let _ = network_event_sender.send(ServerNetworkEvent::Disconnected(id));
+}
+
+impl ServerNetworkSerializationContext for ServerWorld {
+    async fn serialize_gamemode_state(
+        &self,
+        buffer: &mut Vec<u8>,
+        kind: PacketKind,
+    ) -> Result<(), CommonError> {
+        let gamemode = self.server_gamemode_state().read().await;
+
+        if let Some(state) = gamemode.as_ref() {
+            state.serialize(buffer, kind)?;
+        } else {
+            error!("Gamemode unknown for serialization.");
+        }
+
+        Ok(())
+    }
+
+    async fn current_map_moving_platforms_length(&self) -> u8 {
+        self.map_info().read().await.data.moving_platforms_length()
+    }
+}
@@ -0,0 +1,753 @@
+use crate::prelude::*;
+
+use super::io::ServerNetworkEvent;
+
+impl ServerGame {
+    async fn handle_network_events(&self, network_server: &mut NetworkServer) {
+        while let Ok(event) = network_server.network_events.receiver.try_recv() {
+            match event {
+                ServerNetworkEvent::Connected(stream, address) => {
+                    let id = network_server.add_connection(stream, address);
+                    info!("Connection {id} opened from {address}");
+                }
+                ServerNetworkEvent::Disconnected(id) => {
+                    debug!("Network Event: Connection {id} disconnected");
+
+                    if let Err(error) = self.disconnect(network_server, id).await {
+                        error!("{error}");
+                    }
+                }
+                ServerNetworkEvent::Error(error) => warn!("Network Event: {error}"),
+            }
+        }
+    }
+
+    pub async fn update_network_server(&self) -> Result<(), ServerError> {
+        let mut network_server = self.world.network_server().write().await;
+        self.handle_network_events(&mut network_server).await;
+
+        for id in network_server.connection_ids() {
+            // Stops once the connection is closed
+            while let Ok(connection) = network_server.connection(id) {
+                // The password is sent without a packet kind
+                let result = if connection.state == ClientConnectionState::AwaitingPassword {
+                    match network_server.pop_message(id).await {
+                        Ok(Some(message)) => {
+                            self.client_password(&mut network_server, id, message).await
+                        }
+                        Ok(None) => break,
+                        Err(error) => Err(error.into()),
+                    }
+                } else {
+                    match network_server.pop_message(id).await {
+                        Ok(Some(message)) => {
+                            self.client_message(&mut network_server, id, message).await
+                        }
+                        Ok(None) => break,
+                        Err(error) => Err(error.into()),
+                    }
+                };
+
+                if let Err(error) = result {
+                    warn!("Connection {id}: {error}; disconnecting...");
+
+                    // One bad connection mustn't stop the others being served
+                    if let Err(error) = self.disconnect(&mut network_server, id).await {
+                        error!("{error}");
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Server recieved the password it requested
+    async fn client_password(
+        &self,
+        network_server: &mut NetworkServer,
+        id: ConnectionId,
+        message: ClientPassword,
+    ) -> Result<(), ServerError> {
+        if Some(&message.password) == self.world.server_cli_arguments().password.as_ref() {
+            self.send_hello(network_server, id).await?;
+        } else {
+            info!("Connection {id} sent the wrong password");
+            network_server
+                .send_message(id, ServerPasswordWrong, self.world)
+                .await?;
+            self.disconnect(network_server, id).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Server recieved client message
+    async fn client_message(
+        &self,
+        network_server: &mut NetworkServer,
+        id: ConnectionId,
+        generic_message: ClientMessageGeneric,
+    ) -> Result<(), ServerError> {
+        match generic_message {
+            ClientMessageGeneric::Hello(message) => {
+                if message.protocol != PROTOCOL_UUID {
+                    info!("Connection {id} doesn't support the server's protocol");
+                    network_server
+                        .send_message(id, ServerIncompatibleProtocol, self.world)
+                        .await?;
+                    self.disconnect(network_server, id).await?;
+                } else if self.world.server_cli_arguments().password.is_some() {
+                    debug!("Requesting password from connection {id}");
+                    network_server
+                        .send_message(id, ServerPasswordRequest, self.world)
+                        .await?;
+                    network_server.connection_mut(id)?.state =
+                        ClientConnectionState::AwaitingPassword;
+                } else {
+                    self.send_hello(network_server, id).await?;
+                }
+            }
+            ClientMessageGeneric::ReserveSlot(message) => {
+                let slots_taken = network_server
+                    .connections()
+                    .iter()
+                    .filter(|connection| connection.has_slot())
+                    .count();
+
+                if slots_taken >= self.world.server_cli_arguments().max_players.into() {
+                    info!("Server full; turning away connection {id}");
+                    network_server
+                        .send_message(id, ServerServerFull, self.world)
+                        .await?;
+                    self.disconnect(network_server, id).await?;
+                } else {
+                    debug!("Connection {id} reserved a slot");
+                    let connection = network_server.connection_mut(id)?;
+                    connection.player_name = Some(message.player_name);
+                    connection.state = ClientConnectionState::AwaitingPlayerJoin;
+
+                    network_server
+                        .send_message(id, ServerReserveSlot, self.world)
+                        .await?;
+                }
+            }
+            ClientMessageGeneric::DownloadMap(_) => {
+                debug!("Sending map to connection {id}");
+                let map_info = self.world.map_info().read().await;
+                let length = map_info
+                    .file
+                    .len()
+                    .try_into()
+                    .map_err(|_| NetworkError::PacketPayload)?;
+
+                network_server
+                    .send_message(id, ServerDownloadMap { length }, self.world)
+                    .await?;
+                network_server.send_raw(id, map_info.file.clone())?;
+            }
+            ClientMessageGeneric::PlayerJoin(_) => self.player_join(network_server, id).await?,
+            ClientMessageGeneric::Ping(_) => {
+                network_server
+                    .send_message(id, ServerPing, self.world)
+                    .await?;
+            }
+            ClientMessageGeneric::PluginPacket(message) => {
+                // No plugins are loaded, so no plugin ids are known
+                info!(
+                    "Connection {id} sent a packet for unknown plugin {}; kicking...",
+                    message.plugin_id
+                );
+                network_server
+                    .send_message(id, ServerKickBadPluginPacket, self.world)
+                    .await?;
+                self.disconnect(network_server, id).await?;
+            }
+            message => {
+                let player_id = network_server
+                    .connection(id)?
+                    .player_id
+                    .ok_or(CommonError::PlayerIdInvalid)?;
+
+                self.game.client_message(message.clone(), player_id).await?;
+
+                match message {
+                    ClientMessageGeneric::PlayerChangeClass(message) => {
+                        network_server
+                            .broadcast_message(
+                                ServerPlayerChangeClass {
+                                    player_id,
+                                    player_class: message.class,
+                                },
+                                self.world,
+                            )
+                            .await?;
+                        self.spawn_player(network_server, player_id).await?;
+                    }
+                    ClientMessageGeneric::PlayerChangeTeam(message) => {
+                        network_server
+                            .broadcast_message(
+                                ServerPlayerChangeTeam {
+                                    player_id,
+                                    player_team: message.team,
+                                },
+                                self.world,
+                            )
+                            .await?;
+                        self.spawn_player(network_server, player_id).await?;
+                    }
+                    // Inputs are sent out every tick
+                    _ => (),
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn send_hello(
+        &self,
+        network_server: &mut NetworkServer,
+        id: ConnectionId,
+    ) -> Result<(), ServerError> {
+        let hello = {
+            let map_info = self.world.map_info().read().await;
+
+            ServerHello {
+                server_name: self.world.server_cli_arguments().server_name.clone(),
+                map_name: map_info.name.clone(),
+                map_md5: map_info.md5,
+                plugins_required: false,
+                plugins: Vec::new(),
+            }
+        };
+
+        network_server.send_message(id, hello, self.world).await?;
+        network_server.connection_mut(id)?.state = ClientConnectionState::AwaitingReserveSlot;
+
+        Ok(())
+    }
+
+    /// Adds the reserved player and catches the client up on the game
+    async fn player_join(
+        &self,
+        network_server: &mut NetworkServer,
+        id: ConnectionId,
+    ) -> Result<(), ServerError> {
+        let connection = network_server.connection_mut(id)?;
+        let player_name = connection
+            .player_name
+            .take()
+            .ok_or(NetworkError::IncorrectMessage(PacketKind::PlayerJoin))?;
+
+        let (player_id, players) = {
+            let mut players = self.world.server_players().write().await;
+            let player_id = players.push(Player::from_name(player_name.clone()))?;
+
+            (player_id, players.as_vec().clone())
+        };
+
+        connection.player_id = Some(player_id);
+        info!("Player {player_name:?} joined with id {player_id}");
+
+        network_server
+            .send_message(
+                id,
+                ServerJoinUpdate {
+                    client_player_id: player_id,
+                    map_area: 1,
+                },
+                self.world,
+            )
+            .await?;
+
+        let change_map = {
+            let map_info = self.world.map_info().read().await;
+
+            ServerChangeMap {
+                map_name: map_info.name.clone(),
+                map_md5: map_info.md5,
+            }
+        };
+
+        network_server
+            .send_message(id, change_map, self.world)
+            .await?;
+
+        for (index, player) in players.into_iter().enumerate() {
+            let other_id = PlayerId::try_from(index)?;
+
+            network_server
+                .send_message(
+                    id,
+                    ServerPlayerJoin {
+                        player_name: player.name,
+                    },
+                    self.world,
+                )
+                .await?;
+            network_server
+                .send_message(
+                    id,
+                    ServerPlayerChangeTeam {
+                        player_id: other_id,
+                        player_team: player.team,
+                    },
+                    self.world,
+                )
+                .await?;
+            network_server
+                .send_message(
+                    id,
+                    ServerPlayerChangeClass {
+                        player_id: other_id,
+                        player_class: player.class,
+                    },
+                    self.world,
+                )
+                .await?;
+        }
+
+        network_server
+            .send_message(id, self.full_update().await, self.world)
+            .await?;
+
+        // Sent before the new client is in game so it isn't told about itself twice
+        network_server
+            .broadcast_message(ServerPlayerJoin { player_name }, self.world)
+            .await?;
+
+        network_server.connection_mut(id)?.state = ClientConnectionState::InGame;
+
+        Ok(())
+    }
+
+    /// Moves a player on a team to one of their team's spawns
+    async fn spawn_player(
+        &self,
+        network_server: &NetworkServer,
+        player_id: PlayerId,
+    ) -> Result<(), ServerError> {
+        let spawn_group = 0;
+        let spawn_index;
+
+        {
+            let mut players = self.world.server_players().write().await;
+            let player = players.get_mut(player_id)?;
+
+            let Ok(team) = TeamSpawnable::try_from(&player.team) else {
+                // Spectators don't spawn
+                return Ok(());
+            };
+
+            let map_info = self.world.map_info().read().await;
+            let spawn_count = match team {
+                TeamSpawnable::Red => &map_info.data.red_spawns,
+                TeamSpawnable::Blu => &map_info.data.blu_spawns,
+            }[spawn_group as usize]
+                .len();
+
+            spawn_index = (usize::from(player_id) % spawn_count.max(1)) as u8;
+
+            let position = map_info
+                .data
+                .get_spawn_position(&team, spawn_group, spawn_index)?;
+
+            player.transform.translation = Vec3::from((*position, 0.0));
+            player.velocity = Vec2::ZERO;
+        }
+
+        network_server
+            .broadcast_message(
+                ServerPlayerSpawn {
+                    player_id,
+                    spawn_index,
+                    spawn_group,
+                },
+                self.world,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Closes a connection and removes its player
+    async fn disconnect(
+        &self,
+        network_server: &mut NetworkServer,
+        id: ConnectionId,
+    ) -> Result<(), ServerError> {
+        let Some(connection) = network_server.remove_connection(id) else {
+            return Ok(());
+        };
+
+        info!("Connection {id} from {} closed", connection.address);
+
+        let Some(player_id) = connection.player_id else {
+            return Ok(());
+        };
+
+        let player = self
+            .world
+            .server_players()
+            .write()
+            .await
+            .remove(player_id)?;
+
+        info!("Player {:?} left", player.name);
+
+        // Player ids are indices so everyone after shifts down
+        for connection in network_server.connections_mut() {
+            if let Some(other_id) = connection.player_id
+                && other_id > player_id
+            {
+                connection.player_id = Some(PlayerId::try_from(u8::from(other_id) - 1)?);
+            }
+        }
+
+        network_server
+            .broadcast_message(ServerPlayerLeave { player_id }, self.world)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, path::PathBuf, time::Duration};
+
+    use gg2_client::networking::message::{
+        ClientNetworkDeserializationContext, ClientNetworkDeserialize, ClientNetworkSerialize,
+        server::ClientNetworkDeserializeHud,
+    };
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    use super::*;
+
+    struct TestContext {
+        players_length: u8,
+    }
+
+    impl ClientNetworkDeserializationContext for TestContext {
+        fn players_length(&self) -> u8 {
+            self.players_length
+        }
+
+        fn deserialize_gamemode_state(
+            &mut self,
+            payload: &mut MessageReader,
+            _kind: PacketKind,
+        ) -> Result<(), CommonError> {
+            HudMatchTimer::deserialize(payload).map(|_| ())
+        }
+
+        fn current_map_control_points_length(&self) -> Result<u8, CommonError> {
+            Ok(0)
+        }
+
+        fn current_map_moving_platforms_length(&self) -> Result<u8, CommonError> {
+            Ok(0)
+        }
+    }
+
+    struct TestClient {
+        stream: TcpStream,
+        buffer: Vec<u8>,
+        context: TestContext,
+    }
+
+    impl TestClient {
+        async fn connect(address: SocketAddr) -> Self {
+            Self {
+                stream: TcpStream::connect(address).await.unwrap(),
+                buffer: Vec::new(),
+                context: TestContext { players_length: 0 },
+            }
+        }
+
+        async fn send(&mut self, message: ClientMessageGeneric) {
+            let mut buffer = vec![message.as_packet_kind().into()];
+            message.serialize(&mut buffer).await.unwrap();
+            self.stream.write_all(&buffer).await.unwrap();
+        }
+
+        /// The password is sent without a packet kind
+        async fn send_password(&mut self, message: ClientPassword) {
+            let mut buffer = Vec::new();
+            message.serialize(&mut buffer).await.unwrap();
+            self.stream.write_all(&buffer).await.unwrap();
+        }
+
+        async fn receive(&mut self) -> ServerMessageGeneric {
+            loop {
+                let mut payload = MessageReader::new(&self.buffer);
+
+                match ServerMessageGeneric::deserialize(&mut payload, &mut self.context) {
+                    Ok(message) => {
+                        let consumed = payload.consumed();
+                        self.buffer.drain(..consumed);
+
+                        if let ServerMessageGeneric::PlayerJoin(_) = message {
+                            self.context.players_length += 1;
+                        }
+
+                        return message;
+                    }
+                    Err(CommonError::Network(NetworkError::UnexpectedEOF)) => (),
+                    Err(error) => panic!("{error}"),
+                }
+
+                let mut chunk = [0; 1024];
+                let length =
+                    tokio::time::timeout(Duration::from_secs(5), self.stream.read(&mut chunk))
+                        .await
+                        .expect("Timed out waiting for the server")
+                        .unwrap();
+
+                assert_ne!(length, 0, "Server closed the connection");
+                self.buffer.extend(&chunk[..length]);
+            }
+        }
+
+        /// Reads bytes sent outside of any message
+        async fn receive_raw(&mut self, length: usize) -> Vec<u8> {
+            while self.buffer.len() < length {
+                let mut chunk = [0; 1024];
+                let read =
+                    tokio::time::timeout(Duration::from_secs(5), self.stream.read(&mut chunk))
+                        .await
+                        .expect("Timed out waiting for the server")
+                        .unwrap();
+
+                assert_ne!(read, 0, "Server closed the connection");
+                self.buffer.extend(&chunk[..read]);
+            }
+
+            self.buffer.drain(..length).collect()
+        }
+
+        /// Skips the per tick updates
+        async fn receive_event(&mut self) -> ServerMessageGeneric {
+            loop {
+                match self.receive().await {
+                    ServerMessageGeneric::InputState(_) | ServerMessageGeneric::QuickUpdate(_) => {}
+                    message => return message,
+                }
+            }
+        }
+    }
+
+    const TEST_MAP_FILE: &[u8] = b"Not really a PNG";
+
+    async fn start_server(password: Option<&str>, max_players: u8) -> SocketAddr {
+        let spawns = || std::array::from_fn(|_| vec![Vec2::new(10.0, 20.0)]);
+
+        let world = Box::leak(Box::new(ServerWorld::new(
+            ServerCliArguments {
+                map: PathBuf::new(),
+                port: 0,
+                server_name: "Test Server".parse().unwrap(),
+                password: password.map(|password| password.parse().unwrap()),
+                max_players,
+            },
+            MapInfo {
+                name: "test_map".parse().unwrap(),
+                md5: Some(map_md5(TEST_MAP_FILE)),
+                data: MapData {
+                    walk_mask: Vec::new(),
+                    blu_spawns: spawns(),
+                    red_spawns: spawns(),
+                    gamemode: Gamemode::CaptureTheFlag,
+                    control_points: Vec::new(),
+                    moving_platforms: Vec::new(),
+                },
+                file: TEST_MAP_FILE.to_vec(),
+            },
+        )));
+
+        let address = world
+            .network_server()
+            .write()
+            .await
+            .listen(0)
+            .await
+            .unwrap();
+        tokio::spawn(ServerGame::new(world).start_update());
+
+        SocketAddr::from(([127, 0, 0, 1], address.port()))
+    }
+
+    async fn reserve_slot(client: &mut TestClient) -> ServerMessageGeneric {
+        client
+            .send(ClientMessageGeneric::ReserveSlot(ClientReserveSlot {
+                player_name: "Ferris".parse().unwrap(),
+            }))
+            .await;
+        client.receive().await
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn join_and_spawn() {
+        let address = start_server(None, 24).await;
+        let mut client = TestClient::connect(address).await;
+
+        client
+            .send(ClientMessageGeneric::Hello(ClientHello::default()))
+            .await;
+        let ServerMessageGeneric::Hello(hello) = client.receive().await else {
+            panic!("Expected hello");
+        };
+        assert_eq!(hello.server_name, "Test Server".parse().unwrap());
+        assert_eq!(hello.map_name, "test_map".parse().unwrap());
+
+        assert!(matches!(
+            reserve_slot(&mut client).await,
+            ServerMessageGeneric::ReserveSlot(_)
+        ));
+
+        client
+            .send(ClientMessageGeneric::PlayerJoin(ClientPlayerJoin))
+            .await;
+
+        let kinds = [
+            PacketKind::JoinUpdate,
+            PacketKind::ChangeMap,
+            PacketKind::PlayerJoin,
+            PacketKind::PlayerChangeTeam,
+            PacketKind::PlayerChangeClass,
+            PacketKind::FullUpdate,
+        ];
+
+        for kind in kinds {
+            assert_eq!(client.receive().await.as_packet_kind(), kind);
+        }
+
+        client
+            .send(ClientMessageGeneric::PlayerChangeTeam(
+                ClientPlayerChangeTeam { team: Team::Red },
+            ))
+            .await;
+
+        let ServerMessageGeneric::PlayerChangeTeam(change_team) = client.receive_event().await
+        else {
+            panic!("Expected team change");
+        };
+        assert_eq!(change_team.player_team, Team::Red);
+
+        assert!(matches!(
+            client.receive_event().await,
+            ServerMessageGeneric::PlayerSpawn(_)
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn download_map() {
+        let address = start_server(None, 24).await;
+        let mut client = TestClient::connect(address).await;
+
+        client
+            .send(ClientMessageGeneric::Hello(ClientHello::default()))
+            .await;
+        let ServerMessageGeneric::Hello(hello) = client.receive().await else {
+            panic!("Expected hello");
+        };
+
+        client
+            .send(ClientMessageGeneric::DownloadMap(ClientDownloadMap))
+            .await;
+        let ServerMessageGeneric::DownloadMap(download) = client.receive().await else {
+            panic!("Expected map download");
+        };
+
+        let file = client.receive_raw(download.length as usize).await;
+        assert_eq!(file, TEST_MAP_FILE);
+        assert_eq!(hello.map_md5, Some(map_md5(&file)));
+
+        assert!(matches!(
+            reserve_slot(&mut client).await,
+            ServerMessageGeneric::ReserveSlot(_)
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn kick_plugin_packet() {
+        let address = start_server(None, 24).await;
+        let mut client = TestClient::connect(address).await;
+
+        client
+            .send(ClientMessageGeneric::Hello(ClientHello::default()))
+            .await;
+        let ServerMessageGeneric::Hello(hello) = client.receive().await else {
+            panic!("Expected hello");
+        };
+        assert!(hello.plugins.is_empty());
+
+        reserve_slot(&mut client).await;
+        client
+            .send(ClientMessageGeneric::PlayerJoin(ClientPlayerJoin))
+            .await;
+        client
+            .send(ClientMessageGeneric::PluginPacket(ClientPluginPacket {
+                plugin_id: 0,
+                payload: b"hello".to_vec(),
+            }))
+            .await;
+
+        // Skips the join messages sent before the kick
+        while !matches!(
+            client.receive_event().await,
+            ServerMessageGeneric::KickBadPluginPacket(_)
+        ) {}
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn wrong_password() {
+        let address = start_server(Some("hunter2"), 24).await;
+        let mut client = TestClient::connect(address).await;
+
+        client
+            .send(ClientMessageGeneric::Hello(ClientHello::default()))
+            .await;
+        assert!(matches!(
+            client.receive().await,
+            ServerMessageGeneric::PasswordRequest(_)
+        ));
+
+        client
+            .send_password(ClientPassword {
+                password: "hunter3".parse().unwrap(),
+            })
+            .await;
+        assert!(matches!(
+            client.receive().await,
+            ServerMessageGeneric::PasswordWrong(_)
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn server_full() {
+        let address = start_server(None, 1).await;
+        let mut first = TestClient::connect(address).await;
+        let mut second = TestClient::connect(address).await;
+
+        for client in [&mut first, &mut second] {
+            client
+                .send(ClientMessageGeneric::Hello(ClientHello::default()))
+                .await;
+            client.receive().await;
+        }
+
+        assert!(matches!(
+            reserve_slot(&mut first).await,
+            ServerMessageGeneric::ReserveSlot(_)
+        ));
+        assert!(matches!(
+            reserve_slot(&mut second).await,
+            ServerMessageGeneric::ServerFull(_)
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+use crate::prelude::*;
+
+#[derive(Debug, Default)]
+pub struct ServerPlayers {
+    players: Vec<Player>,
+}
+
+impl ServerPlayers {
+    /// The player's character as sent in updates
+    /// Only players on a team have characters
+    pub fn character(player: &Player) -> Option<(RawInput, RawPlayerInfo)> {
+        player.team.is_spawnable().then(|| {
+            let player_info = RawPlayerInfo {
+                translation: player.transform.translation.xy(),
+                velocity: player.velocity,
+                // TODO: Track health
+                health: 100,
+                ammo_count: 0,
+                move_status: 0,
+            };
+
+            (player.input_state.clone(), player_info)
+        })
+    }
+}
+
+impl Players for ServerPlayers {
+    #[inline]
+    fn as_vec(&self) -> &Vec<Player> {
+        &self.players
+    }
+
+    #[inline]
+    fn as_vec_mut(&mut self) -> &mut Vec<Player> {
+        &mut self.players
+    }
+
+    #[inline]
+    fn into_vec(self) -> Vec<Player> {
+        self.players
+    }
+}
+
+impl<'a> IntoIterator for &'a ServerPlayers {
+    type Item = &'a Player;
+    type IntoIter = std::slice::Iter<'a, Player>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.players.iter()
+    }
+}
@@ -0,0 +1,26 @@
+pub use gg2_common::{
+    networking::PROTOCOL_UUID,
+    player::{
+        RawPlayerInfo,
+        team::{Captures, TeamSpawnable},
+    },
+    string::GGStringLong,
+};
+pub use gg2_custom_common::prelude::*;
+pub use gg2_server::networking::{
+    message::{
+        ServerNetworkSerializationContext, ServerNetworkSerialize,
+        server::ServerNetworkSerializeHud,
+    },
+    state::ClientConnectionState,
+};
+
+pub use crate::{
+    error::ServerError,
+    game::{ServerGame, gamemode::ServerGamemodeState},
+    init::cli::ServerCliArguments,
+    map::MapInfo,
+    networking::io::{ConnectionId, NetworkServer},
+    player::ServerPlayers,
+    world::ServerWorld,
+};
@@ -0,0 +1,128 @@
+use std::{pin::Pin, time::Duration};
+
+use atomic_float::AtomicF32;
+
+use crate::prelude::*;
+
+/// How long a match lasts before the round ends
+const MATCH_LENGTH: Duration = Duration::from_mins(15);
+/// How long it takes for a player to respawn
+const RESPAWN_TIME: Duration = Duration::from_secs(5);
+
+pub struct ServerWorld {
+    server_cli_arguments: ServerCliArguments,
+    map_info: RwLock<MapInfo>,
+    network_server: RwLock<NetworkServer>,
+    players: RwLock<ServerPlayers>,
+    round_state: RwLock<RoundState>,
+    moving_platforms: RwLock<Vec<MovingPlatform>>,
+    gamemode_state: RwLock<Option<Box<dyn ServerGamemodeState>>>,
+    delta_tick: AtomicF32,
+}
+
+impl ServerWorld {
+    #[inline]
+    #[must_use]
+    pub fn new(server_cli_arguments: ServerCliArguments, map_info: MapInfo) -> Self {
+        let match_timer = HudMatchTimer {
+            total: MATCH_LENGTH,
+            left: MATCH_LENGTH,
+        };
+
+        let moving_platforms = MovingPlatform::from_map(&map_info.data);
+
+        Self {
+            server_cli_arguments,
+            map_info: map_info.into(),
+            network_server: NetworkServer::default().into(),
+            players: ServerPlayers::default().into(),
+            round_state: RoundState {
+                captures: Captures {
+                    respawn_time: RESPAWN_TIME,
+                    ..Default::default()
+                },
+                round_over: None,
+            }
+            .into(),
+            moving_platforms: moving_platforms.into(),
+            // TODO: Use the map's gamemode
+            gamemode_state: RwLock::new(Some(Box::new(CaptureTheFlagState { match_timer }))),
+            delta_tick: AtomicF32::new(GAME_LOOP_INTERVAL),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn server_cli_arguments(&self) -> &ServerCliArguments {
+        &self.server_cli_arguments
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn map_info(&self) -> &RwLock<MapInfo> {
+        &self.map_info
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn network_server(&self) -> &RwLock<NetworkServer> {
+        &self.network_server
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn server_players(&self) -> &RwLock<ServerPlayers> {
+        &self.players
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn server_gamemode_state(&self) -> &RwLock<Option<Box<dyn ServerGamemodeState>>> {
+        &self.gamemode_state
+    }
+}
+
+impl World for ServerWorld {
+    #[inline]
+    fn players(&self) -> &RwLock<dyn Players> {
+        &self.players
+    }
+
+    #[inline]
+    fn round_state(&self) -> &RwLock<RoundState> {
+        &self.round_state
+    }
+
+    #[inline]
+    fn moving_platforms(&self) -> &RwLock<Vec<MovingPlatform>> {
+        &self.moving_platforms
+    }
+
+    fn delta_tick(&self) -> f32 {
+        self.delta_tick.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    fn set_delta_tick(&self, seconds: f32) {
+        self.delta_tick
+            .store(seconds, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    fn with_gamemode_state_mut(
+        &self,
+        function: Box<
+            dyn FnOnce(
+                    Option<&mut dyn GamemodeState>,
+                )
+                    -> Pin<Box<dyn Future<Output = Result<(), CommonError>> + '_ + Send>>
+                + Send,
+        >,
+    ) -> Pin<Box<dyn Future<Output = Result<(), CommonError>> + '_ + Send>> {
+        Box::pin(async {
+            let mut gamemode_state = self.gamemode_state.write().await;
+            let gamemode_state_generic = gamemode_state
+                .as_deref_mut()
+                .map(|x| x as &mut dyn GamemodeState);
+            function(gamemode_state_generic).await
+        })
+    }
+}
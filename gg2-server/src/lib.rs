@@ -0,0 +1,3 @@
+#![feature(macro_metavar_expr_concat)]
+
+pub mod networking;
@@ -0,0 +1,58 @@
+use gg2_common::{
+    error::Result,
+    networking::{PacketKind, message::MessageReader},
+};
+
+use super::state::ClientConnectionState;
+
+pub mod client;
+pub mod server;
+
+pub trait ServerNetworkSerialize: Sized {
+    fn serialize<C>(self, buffer: &mut Vec<u8>, context: &C) -> impl Future<Output = Result<()>>
+    where
+        C: ServerNetworkSerializationContext;
+}
+
+pub trait ServerNetworkSerializationContext {
+    fn serialize_gamemode_state(
+        &self,
+        buffer: &mut Vec<u8>,
+        kind: PacketKind,
+    ) -> impl Future<Output = Result<()>>;
+
+    fn current_map_moving_platforms_length(&self) -> impl Future<Output = u8>;
+}
+
+pub trait ServerNetworkDeserialize: Sized {
+    fn deserialize<C>(payload: &mut MessageReader, context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext;
+}
+
+pub trait ServerNetworkDeserializationContext {
+    /// How far the sending client is through joining
+    fn connection_state(&self) -> ClientConnectionState;
+}
+
+impl ServerNetworkDeserializationContext for ClientConnectionState {
+    #[inline]
+    fn connection_state(&self) -> ClientConnectionState {
+        *self
+    }
+}
+
+/// Polls a future that never waits to completion
+#[cfg(test)]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    use std::task::{Context, Poll, Waker};
+
+    let mut future = std::pin::pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
@@ -0,0 +1,533 @@
+use gg2_common::{
+    error::{CommonError, Result},
+    networking::{PacketKind, error::NetworkError as Error, message::*},
+    player::RawInput,
+};
+use uuid::Uuid;
+
+use super::{ServerNetworkDeserializationContext, ServerNetworkDeserialize};
+
+macro_rules! generic_message {
+    ($name: ident {$($case: ident),+$(,)?}) => {
+        impl ServerNetworkDeserialize for $name {
+            fn deserialize<C>(payload: &mut MessageReader, context: &C) -> Result<Self> where C: ServerNetworkDeserializationContext {
+                let connection_state = context.connection_state();
+                let raw_kind = payload.read_u8()?;
+                let kind = raw_kind
+                    .try_into()
+                    .map_err(|_| Error::PacketKind(raw_kind))?;
+
+                if !connection_state.allows(kind) {
+                    return Err(CommonError::Network(Error::IncorrectMessage(kind)));
+                }
+
+                match kind {
+                    $(PacketKind::$case => ${concat(Client, $case)}::deserialize(payload, context)
+                        .map(Self::$case)
+                        .map_err(|error| malformed(error, kind))),+,
+                    _ => Err(CommonError::Network(Error::IncorrectMessage(kind))),
+                }
+            }
+        }
+    };
+}
+
+generic_message!(ClientMessageGeneric {
+    ClientSettings,
+    DownloadMap,
+    Hello,
+    InputState,
+    Ping,
+    PlayerChangeClass,
+    PlayerChangeTeam,
+    PlayerJoin,
+    PluginPacket,
+    ReserveSlot,
+    RewardChallengeResponse,
+    RewardRequest,
+});
+
+/// Running out of bytes means the rest of the message hasn't arrived yet;
+/// anything else is a bad payload
+fn malformed(error: CommonError, kind: PacketKind) -> CommonError {
+    match error {
+        CommonError::Network(Error::UnexpectedEOF) => error,
+        _ => CommonError::Network(Error::IncorrectMessage(kind)),
+    }
+}
+
+impl ServerNetworkDeserialize for ClientClientSettings {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let queue_jump = payload.read_bool()?;
+
+        Ok(Self { queue_jump })
+    }
+}
+
+impl ServerNetworkDeserialize for ClientDownloadMap {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ServerNetworkDeserialize for ClientHello {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let protocol = Uuid::from_bytes(payload.read_array()?);
+
+        Ok(Self { protocol })
+    }
+}
+
+impl ServerNetworkDeserialize for RawInput {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let key_state = payload.read_u8()?.into();
+        let aim_direction = payload.read_u16()?;
+        let aim_distance = payload.read_fixed_point_u16(2.0)?;
+
+        Ok(Self {
+            key_state,
+            aim_direction,
+            aim_distance,
+        })
+    }
+}
+
+impl ServerNetworkDeserialize for ClientInputState {
+    fn deserialize<C>(payload: &mut MessageReader, context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let input = RawInput::deserialize(payload, context)?;
+
+        Ok(Self { input })
+    }
+}
+
+impl ServerNetworkDeserialize for ClientPassword {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let password = payload.read_utf8_short_string()?;
+
+        Ok(Self { password })
+    }
+}
+
+impl ServerNetworkDeserialize for ClientPing {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ServerNetworkDeserialize for ClientPlayerChangeClass {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let class = payload
+            .read_u8()?
+            .try_into()
+            .map_err(|_| Error::PacketPayload)?;
+
+        Ok(Self { class })
+    }
+}
+
+impl ServerNetworkDeserialize for ClientPlayerChangeTeam {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let team = payload
+            .read_u8()?
+            .try_into()
+            .map_err(|_| Error::PacketPayload)?;
+
+        Ok(Self { team })
+    }
+}
+
+impl ServerNetworkDeserialize for ClientPlayerJoin {
+    fn deserialize<C>(_payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        Ok(Self)
+    }
+}
+
+impl ServerNetworkDeserialize for ClientPluginPacket {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        // The length includes the plugin id
+        let length = payload
+            .read_u16()?
+            .checked_sub(1)
+            .ok_or(Error::PacketPayload)?;
+        let plugin_id = payload.read_u8()?;
+        let payload = payload.read_bytes(length.into())?.to_vec();
+
+        Ok(Self { plugin_id, payload })
+    }
+}
+
+impl ServerNetworkDeserialize for ClientReserveSlot {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let player_name = payload.read_utf8_short_string()?;
+
+        Ok(Self { player_name })
+    }
+}
+
+impl ServerNetworkDeserialize for ClientRewardChallengeResponse {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let response = payload.read_array()?;
+
+        Ok(Self { response })
+    }
+}
+
+impl ServerNetworkDeserialize for ClientRewardRequest {
+    fn deserialize<C>(payload: &mut MessageReader, _context: &C) -> Result<Self>
+    where
+        C: ServerNetworkDeserializationContext,
+    {
+        let reward_name = payload.read_utf8_short_string()?;
+
+        Ok(Self { reward_name })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gg2_client::networking::message::ClientNetworkSerialize;
+    use gg2_common::{
+        networking::AsPacketKind,
+        player::{KeyState, class::ClassGeneric, team::Team},
+        string::GGStringShort,
+    };
+    use proptest::{collection::vec, prelude::*};
+
+    use super::*;
+    use crate::networking::{message::block_on, state::ClientConnectionState};
+
+    /// Serializes the message the same way the client sends it
+    fn client_bytes(message: ClientMessageGeneric) -> Vec<u8> {
+        let mut buffer = vec![message.as_packet_kind().into()];
+        block_on(message.serialize(&mut buffer)).unwrap();
+        buffer
+    }
+
+    fn parse_payload(
+        payload: &mut MessageReader,
+        state: ClientConnectionState,
+    ) -> Result<ClientMessageGeneric> {
+        ClientMessageGeneric::deserialize(payload, &state)
+    }
+
+    fn parse(bytes: Vec<u8>, state: ClientConnectionState) -> Result<ClientMessageGeneric> {
+        parse_payload(&mut MessageReader::new(&bytes), state)
+    }
+
+    fn round_trip(message: ClientMessageGeneric, state: ClientConnectionState) {
+        let bytes = client_bytes(message.clone());
+        let mut payload = MessageReader::new(&bytes);
+        let parsed = parse_payload(&mut payload, state).unwrap();
+
+        assert_eq!(parsed, message);
+        assert!(payload.is_empty(), "Payload wasn't fully read");
+    }
+
+    /// The password is sent without a packet kind
+    fn round_trip_bare(message: ClientPassword) {
+        let mut bytes = Vec::new();
+        block_on(message.clone().serialize(&mut bytes)).unwrap();
+
+        let mut payload = MessageReader::new(&bytes);
+        let parsed =
+            ClientPassword::deserialize(&mut payload, &ClientConnectionState::AwaitingPassword)
+                .unwrap();
+
+        assert_eq!(parsed, message);
+        assert!(payload.is_empty(), "Payload wasn't fully read");
+    }
+
+    fn assert_incorrect(result: Result<ClientMessageGeneric>, expected: PacketKind) {
+        match result {
+            Err(CommonError::Network(Error::IncorrectMessage(kind))) => assert_eq!(kind, expected),
+            other => panic!("Expected incorrect message {expected:?}, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn round_trip_hello() {
+        round_trip(
+            ClientMessageGeneric::Hello(ClientHello::default()),
+            ClientConnectionState::AwaitingHello,
+        );
+    }
+
+    #[test]
+    fn round_trip_password() {
+        round_trip_bare(ClientPassword {
+            password: "hunter2".parse().unwrap(),
+        });
+    }
+
+    #[test]
+    fn round_trip_client_settings() {
+        round_trip(
+            ClientMessageGeneric::ClientSettings(ClientClientSettings { queue_jump: true }),
+            ClientConnectionState::InGame,
+        );
+    }
+
+    #[test]
+    fn round_trip_download_map() {
+        round_trip(
+            ClientMessageGeneric::DownloadMap(ClientDownloadMap),
+            ClientConnectionState::AwaitingReserveSlot,
+        );
+    }
+
+    #[test]
+    fn round_trip_reserve_slot() {
+        round_trip(
+            ClientMessageGeneric::ReserveSlot(ClientReserveSlot {
+                player_name: "Ferris".parse().unwrap(),
+            }),
+            ClientConnectionState::AwaitingReserveSlot,
+        );
+    }
+
+    #[test]
+    fn round_trip_player_join() {
+        round_trip(
+            ClientMessageGeneric::PlayerJoin(ClientPlayerJoin),
+            ClientConnectionState::AwaitingPlayerJoin,
+        );
+    }
+
+    #[test]
+    fn round_trip_input_state() {
+        let mut key_state = KeyState::default();
+        key_state.set_up(true);
+        key_state.set_secondary(true);
+
+        round_trip(
+            ClientMessageGeneric::InputState(ClientInputState {
+                input: RawInput {
+                    key_state,
+                    aim_direction: 40000,
+                    aim_distance: 300.5,
+                },
+            }),
+            ClientConnectionState::InGame,
+        );
+    }
+
+    #[test]
+    fn round_trip_player_change_class() {
+        round_trip(
+            ClientMessageGeneric::PlayerChangeClass(ClientPlayerChangeClass {
+                class: ClassGeneric::Engineer,
+            }),
+            ClientConnectionState::InGame,
+        );
+    }
+
+    #[test]
+    fn round_trip_player_change_team() {
+        round_trip(
+            ClientMessageGeneric::PlayerChangeTeam(ClientPlayerChangeTeam { team: Team::Red }),
+            ClientConnectionState::InGame,
+        );
+    }
+
+    #[test]
+    fn round_trip_plugin_packet() {
+        round_trip(
+            ClientMessageGeneric::PluginPacket(ClientPluginPacket {
+                plugin_id: 0,
+                payload: vec![1, 2, 3],
+            }),
+            ClientConnectionState::InGame,
+        );
+    }
+
+    #[test]
+    fn round_trip_ping() {
+        round_trip(
+            ClientMessageGeneric::Ping(ClientPing),
+            ClientConnectionState::InGame,
+        );
+    }
+
+    #[test]
+    fn round_trip_reward_request() {
+        round_trip(
+            ClientMessageGeneric::RewardRequest(ClientRewardRequest {
+                reward_name: "Ferris".parse().unwrap(),
+            }),
+            ClientConnectionState::InGame,
+        );
+    }
+
+    #[test]
+    fn round_trip_reward_challenge_response() {
+        round_trip(
+            ClientMessageGeneric::RewardChallengeResponse(ClientRewardChallengeResponse {
+                response: std::array::from_fn(|index| index as u8),
+            }),
+            ClientConnectionState::InGame,
+        );
+    }
+
+    #[test]
+    fn reject_out_of_order() {
+        let join = client_bytes(ClientMessageGeneric::PlayerJoin(ClientPlayerJoin));
+        assert_incorrect(
+            parse(join, ClientConnectionState::AwaitingHello),
+            PacketKind::PlayerJoin,
+        );
+
+        let hello = client_bytes(ClientMessageGeneric::Hello(ClientHello::default()));
+        assert_incorrect(
+            parse(hello, ClientConnectionState::InGame),
+            PacketKind::Hello,
+        );
+    }
+
+    #[test]
+    fn reject_server_only_kind() {
+        assert_incorrect(
+            parse(
+                vec![PacketKind::FullUpdate.into()],
+                ClientConnectionState::InGame,
+            ),
+            PacketKind::FullUpdate,
+        );
+    }
+
+    #[test]
+    fn reject_malformed_payload() {
+        assert_incorrect(
+            parse(
+                vec![PacketKind::PlayerChangeClass.into(), u8::MAX],
+                ClientConnectionState::InGame,
+            ),
+            PacketKind::PlayerChangeClass,
+        );
+
+        assert!(
+            ClientPassword::deserialize(
+                &mut MessageReader::new(&[3, 0xFF, 0xFE, 0xFD]),
+                &ClientConnectionState::AwaitingPassword,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn incomplete_payload() {
+        let mut hello = client_bytes(ClientMessageGeneric::Hello(ClientHello::default()));
+        hello.truncate(8);
+
+        assert!(matches!(
+            parse(hello, ClientConnectionState::AwaitingHello),
+            Err(CommonError::Network(Error::UnexpectedEOF))
+        ));
+    }
+
+    #[test]
+    fn reject_unknown_kind() {
+        assert!(matches!(
+            parse(vec![26], ClientConnectionState::InGame),
+            Err(CommonError::Network(Error::PacketKind(26)))
+        ));
+    }
+
+    fn any_short_string() -> impl Strategy<Value = GGStringShort> {
+        "\\PC{0,80}".prop_filter_map("Longer than 255 bytes", |string| string.parse().ok())
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_any_password(password in any_short_string()) {
+            round_trip_bare(ClientPassword { password });
+        }
+
+        #[test]
+        fn round_trip_any_reserve_slot(player_name in any_short_string()) {
+            round_trip(
+                ClientMessageGeneric::ReserveSlot(ClientReserveSlot { player_name }),
+                ClientConnectionState::AwaitingReserveSlot,
+            );
+        }
+
+        #[test]
+        fn round_trip_any_input_state(key_state in any::<u8>(), aim_direction in any::<u16>(), aim_distance in any::<u16>()) {
+            round_trip(
+                ClientMessageGeneric::InputState(ClientInputState {
+                    input: RawInput {
+                        key_state: key_state.into(),
+                        aim_direction,
+                        // Values that survive being written as a fixed point `u16`
+                        aim_distance: f32::from(aim_distance) / 2.0,
+                    },
+                }),
+                ClientConnectionState::InGame,
+            );
+        }
+
+        #[test]
+        fn round_trip_any_plugin_packet(plugin_id in any::<u8>(), payload in vec(any::<u8>(), 0..1024)) {
+            round_trip(
+                ClientMessageGeneric::PluginPacket(ClientPluginPacket { plugin_id, payload }),
+                ClientConnectionState::InGame,
+            );
+        }
+
+        #[test]
+        fn round_trip_any_reward_request(reward_name in any_short_string()) {
+            round_trip(
+                ClientMessageGeneric::RewardRequest(ClientRewardRequest { reward_name }),
+                ClientConnectionState::InGame,
+            );
+        }
+
+        #[test]
+        fn round_trip_any_reward_challenge_response(response in any::<[u8; 16]>()) {
+            round_trip(
+                ClientMessageGeneric::RewardChallengeResponse(ClientRewardChallengeResponse {
+                    response,
+                }),
+                ClientConnectionState::InGame,
+            );
+        }
+    }
+}
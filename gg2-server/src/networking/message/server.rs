@@ -0,0 +1,1800 @@
+use gg2_common::{
+    error::Result,
+    game::{
+        control_point::RawControlPoint, generator::RawGenerator, intel::RawIntel,
+        moving_platform::RawMovingPlatform,
+    },
+    hud::{GamemodeHudArenaFull, HudKothTimer, HudMatchTimer},
+    networking::{error::NetworkError as Error, message::*, plugin::format_plugin_list},
+    player::{RawAdditionalPlayerInfo, RawInput, RawPlayerInfo, team::Captures},
+};
+
+use super::{ServerNetworkSerializationContext, ServerNetworkSerialize};
+
+macro_rules! generic_message {
+    ($name: ident {$($case: ident),+$(,)?}) => {
+        impl ServerNetworkSerialize for $name {
+            async fn serialize<C>(self, buffer: &mut Vec<u8>, context: &C) -> Result<()> where C: ServerNetworkSerializationContext {
+                match self {
+                    $(Self::$case(message) => (message.serialize(buffer, context).await?)),+,
+                }
+
+                Ok(())
+            }
+        }
+    };
+}
+
+generic_message!(ServerMessageGeneric {
+    Hello,
+    PlayerJoin,
+    PlayerLeave,
+    PlayerChangeTeam,
+    PlayerChangeClass,
+    PlayerSpawn,
+    InputState,
+    ChangeMap,
+    FullUpdate,
+    QuickUpdate,
+    PlayerDeath,
+    ServerFull,
+    RedTeamCap,
+    BlueTeamCap,
+    MapEnd,
+    ChatBubble,
+    BuildSentry,
+    DestroySentry,
+    Balance,
+    GrabIntel,
+    ScoreIntel,
+    DropIntel,
+    UberCharged,
+    Uber,
+    Omnom,
+    PasswordRequest,
+    PasswordWrong,
+    CaptureUpdate,
+    CpCaptured,
+    PlayerChangeName,
+    GeneratorDestroy,
+    ArenaWaitForPlayers,
+    ArenaEndround,
+    ArenaRestart,
+    UnlockCp,
+    ArenaStartround,
+    ServerKick,
+    Kick,
+    KickName,
+    ToggleZoom,
+    ReturnIntel,
+    IncompatibleProtocol,
+    JoinUpdate,
+    DownloadMap,
+    SentryPosition,
+    RewardUpdate,
+    RewardChallengeCode,
+    MessageString,
+    WeaponFire,
+    PluginPacket,
+    KickBadPluginPacket,
+    Ping,
+    KickMultiClient,
+    ReserveSlot,
+});
+
+impl ServerNetworkSerialize for Captures {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.red_captures);
+        buffer.write_u8(self.blu_captures);
+        buffer.write_u8(self.respawn_time.as_secs() as u8);
+
+        Ok(())
+    }
+}
+
+/// Gamemode HUD state is written without a context by the gamemode itself
+pub trait ServerNetworkSerializeHud {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<()>;
+}
+
+impl ServerNetworkSerializeHud for HudMatchTimer {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.write_u8(self.total.as_secs().div_euclid(60) as u8);
+        buffer.write_u32((self.left.as_secs_f32() * 30.0).round() as u32);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerializeHud for RawGenerator {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.write_u16(self.health);
+        buffer.write_u16(self.shield_health);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerializeHud for GamemodeHudArenaFull {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.write_u8(self.red_wins);
+        buffer.write_u8(self.blu_wins);
+        buffer.write_u8(self.state);
+        buffer.write_u8(self.winners);
+        buffer.write_u16(self.end_count);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerializeHud for RawControlPoint {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.write_u8(self.team.map_or(u8::MAX, u8::from));
+        buffer.write_u8(self.capturing_team.map_or(u8::MAX, u8::from));
+        buffer.write_u16(self.capturing);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerializeHud for HudKothTimer {
+    fn serialize(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.write_duration_u16_sec(self.capture_unlock);
+        buffer.write_duration_u16_sec(self.red_timer);
+        buffer.write_duration_u16_sec(self.blu_timer);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerArenaEndround {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.winners.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerArenaRestart {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerArenaStartround {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerArenaWaitForPlayers {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerBalance {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.map_or(u8::MAX, u8::from));
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerBlueTeamCap {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerBuildSentry {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+        buffer.write_fixed_point_u16_vec2(self.position, 5.0);
+        buffer.write_u8(if self.looking_left { -1i8 } else { 1 } as u8);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerCaptureUpdate {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_amount);
+        self.captures.serialize(buffer, context).await?;
+        context.serialize_gamemode_state(buffer, Self::KIND).await?;
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerChangeMap {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_utf8_short_string(&self.map_name);
+        buffer.write_md5(self.map_md5);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerChatBubble {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        // TODO: What does this byte do?
+        buffer.write_u8(0);
+        buffer.write_u8(self.bubble.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerCpCaptured {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.control_point);
+        buffer.write_u8(self.team.into());
+
+        let cappers_length = u8::try_from(self.cappers.len()).map_err(Error::ListLength)?;
+        buffer.write_u8(cappers_length);
+
+        for player_id in self.cappers {
+            buffer.write_u8(player_id.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerDestroySentry {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerDownloadMap {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u32(self.length);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerDropIntel {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerGeneratorDestroy {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.team.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerGrabIntel {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for RawInput {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.key_state.into());
+        buffer.write_u16(self.aim_direction);
+        buffer.write_fixed_point_u8(self.aim_distance, 2.0);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for RawPlayerInfo {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_fixed_point_u16_vec2(self.translation, 5.0);
+        buffer.write_fixed_point_u8_vec2(self.velocity, 8.5);
+        buffer.write_u8(self.health);
+        buffer.write_u8(self.ammo_count);
+        buffer.write_u8(self.move_status);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for RawAdditionalPlayerInfo {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_bool(self.intel);
+        buffer.write_u16(self.intel_recharge);
+        buffer.extend(self.class_state);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for PlayerUpdateInfo {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.kills);
+        buffer.write_u8(self.deaths);
+        buffer.write_u8(self.captures);
+        buffer.write_u8(self.assists);
+        buffer.write_u8(self.destruction);
+        buffer.write_u8(self.stabs);
+        buffer.write_u16(self.healing);
+        buffer.write_u8(self.defenses);
+        buffer.write_bool(self.invulnerability);
+        buffer.write_u8(self.bonus);
+        buffer.write_u8(self.points);
+        buffer.write_bool(self.queue_jump);
+        buffer.write_utf8_long_string(&self.rewards);
+        buffer.extend(self.dominations);
+
+        buffer.write_bool(self.character.is_some());
+
+        if let Some((input, player_info, additional_info)) = self.character {
+            input.serialize(buffer, context).await?;
+            player_info.serialize(buffer, context).await?;
+            additional_info.serialize(buffer, context).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for RawIntel {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_fixed_point_u16_vec2(self.position, 5.0);
+        // TODO: Confirm the unit of the intel's recharge time
+        buffer.write_u16((self.recharge_time.as_secs_f32() * 30.0) as i16 as u16);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for RawMovingPlatform {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_fixed_point_u16_vec2(self.translation, 5.0);
+        buffer.write_bool(self.returning);
+
+        Ok(())
+    }
+}
+
+/// Sent without a length, as clients count the platforms in their map
+impl ServerNetworkSerialize for Vec<RawMovingPlatform> {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        if self.len() != usize::from(context.current_map_moving_platforms_length().await) {
+            return Err(Error::PacketPayload.into());
+        }
+
+        for moving_platform in self {
+            moving_platform.serialize(buffer, context).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerFullUpdate {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u16(self.team_death_match_invulnerability_ticks);
+
+        let player_length = u8::try_from(self.player_info.len()).map_err(Error::ListLength)?;
+        buffer.write_u8(player_length);
+
+        for player_info in self.player_info {
+            player_info.serialize(buffer, context).await?;
+        }
+
+        self.moving_platforms.serialize(buffer, context).await?;
+
+        let red_intel_length = u16::try_from(self.red_intel.len()).map_err(Error::ListLength)?;
+        buffer.write_u16(red_intel_length);
+
+        for intel in self.red_intel {
+            intel.serialize(buffer, context).await?;
+        }
+
+        let blu_intel_length = u16::try_from(self.blu_intel.len()).map_err(Error::ListLength)?;
+        buffer.write_u16(blu_intel_length);
+
+        for intel in self.blu_intel {
+            intel.serialize(buffer, context).await?;
+        }
+
+        buffer.write_u8(self.capture_limit);
+        self.captures.serialize(buffer, context).await?;
+        context.serialize_gamemode_state(buffer, Self::KIND).await?;
+
+        buffer.write_u8(self.scout_limit);
+        buffer.write_u8(self.soldier_limit);
+        buffer.write_u8(self.sniper_limit);
+        buffer.write_u8(self.demoman_limit);
+        buffer.write_u8(self.medic_limit);
+        buffer.write_u8(self.engineer_limit);
+        buffer.write_u8(self.heavy_limit);
+        buffer.write_u8(self.spy_limit);
+        buffer.write_u8(self.pyro_limit);
+        buffer.write_u8(self.quote_limit);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerHello {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_utf8_short_string(&self.server_name);
+        buffer.write_utf8_short_string(&self.map_name);
+        buffer.write_md5(self.map_md5);
+
+        buffer.write_bool(self.plugins_required);
+        buffer.write_utf8_long_string(&format_plugin_list(&self.plugins).parse()?);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerIncompatibleProtocol {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerInputState {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        let character_length = u8::try_from(self.inputs.len()).map_err(Error::ListLength)?;
+        buffer.write_u8(character_length);
+
+        for input in self.inputs {
+            buffer.write_bool(input.is_some());
+
+            if let Some(input) = input {
+                input.serialize(buffer, context).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerJoinUpdate {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.client_player_id.into());
+        buffer.write_u8(self.map_area);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerKick {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.reason.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerKickBadPluginPacket {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerKickMultiClient {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerKickName {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerMapEnd {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_utf8_short_string(&self.next_map);
+        buffer.write_u8(self.winners.into());
+        buffer.write_u8(self.map_area);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerMessageString {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_utf8_short_string(&self.message);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerOmnom {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(0);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPasswordRequest {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPasswordWrong {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPing {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPlayerChangeClass {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+        buffer.write_u8(self.player_class.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPlayerChangeName {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+        buffer.write_utf8_short_string(&self.name);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPlayerChangeTeam {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+        buffer.write_u8(self.player_team.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPlayerDeath {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.target.into());
+        buffer.write_u8(self.attacker.map(u8::from).unwrap_or(u8::MAX));
+        buffer.write_u8(self.assist.map(u8::from).unwrap_or(u8::MAX));
+        buffer.write_u8(self.damage_source.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPlayerJoin {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_utf8_short_string(&self.player_name);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPlayerLeave {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPlayerSpawn {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+        buffer.write_u8(self.spawn_index);
+        buffer.write_u8(self.spawn_group);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerPluginPacket {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        // The length includes the plugin id
+        let length = u16::try_from(self.payload.len() + 1).map_err(Error::ListLength)?;
+
+        buffer.write_u16(length);
+        buffer.write_u8(self.plugin_id);
+        buffer.extend(self.payload);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerQuickUpdate {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        let player_length =
+            u8::try_from(self.player_characters.len()).map_err(Error::ListLength)?;
+        buffer.write_u8(player_length);
+
+        for character in self.player_characters {
+            buffer.write_bool(character.is_some());
+
+            if let Some((input, player_info)) = character {
+                input.serialize(buffer, context).await?;
+                player_info.serialize(buffer, context).await?;
+            }
+        }
+
+        self.moving_platforms.serialize(buffer, context).await?;
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerRedTeamCap {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerReserveSlot {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerReturnIntel {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.team.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerRewardChallengeCode {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.extend(self.challenge);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerRewardUpdate {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+        buffer.write_utf8_long_string(&self.rewards);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerScoreIntel {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerSentryPosition {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+        buffer.write_fixed_point_u16_vec2(self.position, 5.0);
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerServerKick {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerServerFull {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerToggleZoom {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerUber {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerUberCharged {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerUnlockCp {
+    async fn serialize<C>(self, _buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        Ok(())
+    }
+}
+
+impl ServerNetworkSerialize for ServerWeaponFire {
+    async fn serialize<C>(self, buffer: &mut Vec<u8>, _context: &C) -> Result<()>
+    where
+        C: ServerNetworkSerializationContext,
+    {
+        buffer.write_u8(self.player_id.into());
+        buffer.write_fixed_point_u16_vec2(self.position, 5.0);
+        buffer.write_fixed_point_u8_vec2(self.velocity, 8.5);
+        buffer.write_u16(self.seed);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use gg2_client::networking::message::{
+        ClientNetworkDeserializationContext, ClientNetworkDeserialize,
+        server::ClientNetworkDeserializeHud,
+    };
+    use gg2_common::{
+        chat::bubble::ChatBubble,
+        damage::source::DamageSource,
+        error::CommonError,
+        networking::{AsPacketKind, KickReason, PacketKind, plugin::ServerPlugin},
+        player::{
+            KeyState, PlayerId,
+            class::ClassGeneric,
+            team::{Team, TeamSpawnable},
+        },
+        string::GGStringShort,
+    };
+    use glam::Vec2;
+    use proptest::{collection::vec, option, prelude::*, sample::select};
+
+    use super::*;
+    use crate::networking::message::block_on;
+
+    /// Writes and reads a CTF match timer as the gamemode state
+    #[derive(Default)]
+    struct TestContext {
+        match_timer: HudMatchTimer,
+        received_match_timer: Option<HudMatchTimer>,
+        moving_platforms_length: u8,
+    }
+
+    impl ServerNetworkSerializationContext for TestContext {
+        async fn serialize_gamemode_state(
+            &self,
+            buffer: &mut Vec<u8>,
+            _kind: PacketKind,
+        ) -> Result<()> {
+            self.match_timer.serialize(buffer)
+        }
+
+        async fn current_map_moving_platforms_length(&self) -> u8 {
+            self.moving_platforms_length
+        }
+    }
+
+    impl ClientNetworkDeserializationContext for TestContext {
+        fn players_length(&self) -> u8 {
+            0
+        }
+
+        fn deserialize_gamemode_state(
+            &mut self,
+            payload: &mut MessageReader,
+            _kind: PacketKind,
+        ) -> Result<()> {
+            self.received_match_timer = Some(HudMatchTimer::deserialize(payload)?);
+
+            Ok(())
+        }
+
+        fn current_map_control_points_length(&self) -> Result<u8> {
+            Ok(0)
+        }
+
+        fn current_map_moving_platforms_length(&self) -> Result<u8> {
+            Ok(self.moving_platforms_length)
+        }
+    }
+
+    fn round_trip_context(message: ServerMessageGeneric, context: &mut TestContext) {
+        let mut buffer = vec![message.as_packet_kind().into()];
+        block_on(message.clone().serialize(&mut buffer, context)).unwrap();
+
+        let mut payload = MessageReader::new(&buffer);
+        let parsed = ServerMessageGeneric::deserialize(&mut payload, context).unwrap();
+
+        assert_eq!(parsed, message);
+        assert!(payload.is_empty(), "Payload wasn't fully read");
+    }
+
+    fn round_trip(message: ServerMessageGeneric) {
+        round_trip_context(message, &mut TestContext::default());
+    }
+
+    fn player_id(id: u8) -> PlayerId {
+        PlayerId::try_from(id).unwrap()
+    }
+
+    fn captures() -> Captures {
+        Captures {
+            red_captures: 2,
+            blu_captures: 1,
+            respawn_time: Duration::from_secs(5),
+        }
+    }
+
+    fn raw_input() -> RawInput {
+        let mut key_state = KeyState::default();
+        key_state.set_left(true);
+        key_state.set_primary(true);
+
+        RawInput {
+            key_state,
+            aim_direction: 12345,
+            aim_distance: 64.5,
+        }
+    }
+
+    fn raw_player_info() -> RawPlayerInfo {
+        RawPlayerInfo {
+            translation: Vec2::new(1142.0, 504.4),
+            velocity: Vec2::new(2.0, 4.0),
+            health: 125,
+            ammo_count: 4,
+            move_status: 1,
+        }
+    }
+
+    #[test]
+    fn round_trip_hello() {
+        round_trip(ServerMessageGeneric::Hello(ServerHello {
+            server_name: "Rust Server".parse().unwrap(),
+            map_name: "ctf_truefort".parse().unwrap(),
+            map_md5: Some(298800483114597941956032572434422514879),
+            plugins_required: true,
+            plugins: vec![ServerPlugin {
+                name: "kothtimer".to_string(),
+                md5: 42,
+            }],
+        }));
+    }
+
+    #[test]
+    fn round_trip_player_join() {
+        round_trip(ServerMessageGeneric::PlayerJoin(ServerPlayerJoin {
+            player_name: "Rust Player".parse().unwrap(),
+        }));
+    }
+
+    #[test]
+    fn round_trip_player_leave() {
+        round_trip(ServerMessageGeneric::PlayerLeave(ServerPlayerLeave {
+            player_id: player_id(3),
+        }));
+    }
+
+    #[test]
+    fn round_trip_player_change_team() {
+        round_trip(ServerMessageGeneric::PlayerChangeTeam(
+            ServerPlayerChangeTeam {
+                player_id: player_id(1),
+                player_team: Team::Blu,
+            },
+        ));
+    }
+
+    #[test]
+    fn round_trip_player_change_class() {
+        round_trip(ServerMessageGeneric::PlayerChangeClass(
+            ServerPlayerChangeClass {
+                player_id: player_id(1),
+                player_class: ClassGeneric::Quote,
+            },
+        ));
+    }
+
+    #[test]
+    fn round_trip_player_spawn() {
+        round_trip(ServerMessageGeneric::PlayerSpawn(ServerPlayerSpawn {
+            player_id: player_id(0),
+            spawn_index: 2,
+            spawn_group: 1,
+        }));
+    }
+
+    #[test]
+    fn round_trip_input_state() {
+        round_trip(ServerMessageGeneric::InputState(ServerInputState {
+            inputs: vec![Some(raw_input()), None, Some(RawInput::default())],
+        }));
+    }
+
+    #[test]
+    fn round_trip_change_map() {
+        round_trip(ServerMessageGeneric::ChangeMap(ServerChangeMap {
+            map_name: "koth_harvest".parse().unwrap(),
+            map_md5: None,
+        }));
+    }
+
+    #[test]
+    fn round_trip_full_update() {
+        let mut context = TestContext {
+            match_timer: HudMatchTimer {
+                total: Duration::from_mins(15),
+                left: Duration::from_secs(600),
+            },
+            ..Default::default()
+        };
+
+        let player_info = PlayerUpdateInfo {
+            kills: 10,
+            deaths: 2,
+            captures: 1,
+            assists: 3,
+            destruction: 4,
+            stabs: 5,
+            healing: 1000,
+            defenses: 6,
+            invulnerability: true,
+            bonus: 7,
+            points: 30,
+            queue_jump: false,
+            rewards: "Rust".to_string().try_into().unwrap(),
+            dominations: vec![1],
+            character: Some((
+                raw_input(),
+                raw_player_info(),
+                RawAdditionalPlayerInfo {
+                    intel: true,
+                    intel_recharge: 90,
+                    class_state: [1, 2, 3, 4, 5, 6],
+                },
+            )),
+        };
+
+        round_trip_context(
+            ServerMessageGeneric::FullUpdate(ServerFullUpdate {
+                team_death_match_invulnerability_ticks: 90,
+                player_info: vec![
+                    player_info.clone(),
+                    PlayerUpdateInfo {
+                        queue_jump: true,
+                        dominations: vec![0],
+                        character: None,
+                        ..player_info
+                    },
+                ],
+                moving_platforms: Vec::new(),
+                red_intel: vec![RawIntel {
+                    position: Vec2::new(100.0, 200.0),
+                    recharge_time: Duration::default(),
+                }],
+                blu_intel: Vec::new(),
+                capture_limit: 3,
+                captures: captures(),
+                scout_limit: 1,
+                soldier_limit: 2,
+                sniper_limit: 3,
+                demoman_limit: 4,
+                medic_limit: 5,
+                engineer_limit: 6,
+                heavy_limit: 7,
+                spy_limit: 8,
+                pyro_limit: 9,
+                quote_limit: 10,
+            }),
+            &mut context,
+        );
+
+        let received = context
+            .received_match_timer
+            .as_ref()
+            .expect("Gamemode state wasn't read");
+        assert_eq!(received.total, context.match_timer.total);
+        assert_eq!(received.left, context.match_timer.left);
+    }
+
+    fn moving_platforms() -> Vec<RawMovingPlatform> {
+        vec![
+            RawMovingPlatform {
+                translation: Vec2::new(320.0, 96.0),
+                returning: false,
+            },
+            RawMovingPlatform {
+                translation: Vec2::new(64.0, 480.0),
+                returning: true,
+            },
+        ]
+    }
+
+    #[test]
+    fn round_trip_quick_update() {
+        round_trip_context(
+            ServerMessageGeneric::QuickUpdate(ServerQuickUpdate {
+                player_characters: vec![None, Some((raw_input(), raw_player_info()))],
+                moving_platforms: moving_platforms(),
+            }),
+            &mut TestContext {
+                moving_platforms_length: 2,
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn reject_moving_platforms_not_in_map() {
+        let message = ServerQuickUpdate {
+            player_characters: Vec::new(),
+            moving_platforms: moving_platforms(),
+        };
+
+        assert!(matches!(
+            block_on(message.serialize(&mut Vec::new(), &TestContext::default())),
+            Err(CommonError::Network(Error::PacketPayload))
+        ));
+    }
+
+    #[test]
+    fn round_trip_player_death() {
+        round_trip(ServerMessageGeneric::PlayerDeath(ServerPlayerDeath {
+            target: player_id(0),
+            attacker: Some(player_id(1)),
+            assist: None,
+            damage_source: DamageSource::Backstab,
+        }));
+    }
+
+    #[test]
+    fn round_trip_server_full() {
+        round_trip(ServerMessageGeneric::ServerFull(ServerServerFull));
+    }
+
+    #[test]
+    fn round_trip_red_team_cap() {
+        round_trip(ServerMessageGeneric::RedTeamCap(ServerRedTeamCap));
+    }
+
+    #[test]
+    fn round_trip_blue_team_cap() {
+        round_trip(ServerMessageGeneric::BlueTeamCap(ServerBlueTeamCap));
+    }
+
+    #[test]
+    fn round_trip_map_end() {
+        round_trip(ServerMessageGeneric::MapEnd(ServerMapEnd {
+            next_map: "ctf_truefort".parse().unwrap(),
+            winners: Team::Red,
+            map_area: 1,
+        }));
+    }
+
+    #[test]
+    fn round_trip_map_end_stalemate() {
+        round_trip(ServerMessageGeneric::MapEnd(ServerMapEnd {
+            next_map: "ctf_2dfort".parse().unwrap(),
+            winners: Team::Spectator,
+            map_area: 0,
+        }));
+    }
+
+    #[test]
+    fn round_trip_chat_bubble() {
+        round_trip(ServerMessageGeneric::ChatBubble(ServerChatBubble {
+            bubble: ChatBubble::EasterEgg,
+        }));
+    }
+
+    #[test]
+    fn round_trip_build_sentry() {
+        round_trip(ServerMessageGeneric::BuildSentry(ServerBuildSentry {
+            player_id: player_id(3),
+            position: Vec2::new(512.0, 96.4),
+            looking_left: true,
+        }));
+        round_trip(ServerMessageGeneric::BuildSentry(ServerBuildSentry {
+            player_id: player_id(3),
+            position: Vec2::new(64.0, 1200.2),
+            looking_left: false,
+        }));
+    }
+
+    #[test]
+    fn round_trip_cp_captured() {
+        round_trip(ServerMessageGeneric::CpCaptured(ServerCpCaptured {
+            control_point: 2,
+            team: TeamSpawnable::Blu,
+            cappers: vec![player_id(0), player_id(4)],
+        }));
+    }
+
+    #[test]
+    fn round_trip_destroy_sentry() {
+        round_trip(ServerMessageGeneric::DestroySentry(ServerDestroySentry {
+            player_id: player_id(3),
+        }));
+    }
+
+    #[test]
+    fn round_trip_balance() {
+        round_trip(ServerMessageGeneric::Balance(ServerBalance {
+            player_id: Some(player_id(2)),
+        }));
+        round_trip(ServerMessageGeneric::Balance(ServerBalance {
+            player_id: None,
+        }));
+    }
+
+    #[test]
+    fn round_trip_generator_destroy() {
+        round_trip(ServerMessageGeneric::GeneratorDestroy(
+            ServerGeneratorDestroy {
+                team: TeamSpawnable::Red,
+            },
+        ));
+    }
+
+    #[test]
+    fn round_trip_grab_intel() {
+        round_trip(ServerMessageGeneric::GrabIntel(ServerGrabIntel {
+            player_id: player_id(4),
+        }));
+    }
+
+    #[test]
+    fn round_trip_score_intel() {
+        round_trip(ServerMessageGeneric::ScoreIntel(ServerScoreIntel {
+            player_id: player_id(4),
+        }));
+    }
+
+    #[test]
+    fn round_trip_drop_intel() {
+        round_trip(ServerMessageGeneric::DropIntel(ServerDropIntel {
+            player_id: player_id(4),
+        }));
+    }
+
+    #[test]
+    fn round_trip_uber_charged() {
+        round_trip(ServerMessageGeneric::UberCharged(ServerUberCharged {
+            player_id: player_id(6),
+        }));
+    }
+
+    #[test]
+    fn round_trip_uber() {
+        round_trip(ServerMessageGeneric::Uber(ServerUber {
+            player_id: player_id(6),
+        }));
+    }
+
+    #[test]
+    fn round_trip_omnom() {
+        round_trip(ServerMessageGeneric::Omnom(ServerOmnom));
+    }
+
+    #[test]
+    fn round_trip_password_request() {
+        round_trip(ServerMessageGeneric::PasswordRequest(ServerPasswordRequest));
+    }
+
+    #[test]
+    fn round_trip_password_wrong() {
+        round_trip(ServerMessageGeneric::PasswordWrong(ServerPasswordWrong));
+    }
+
+    #[test]
+    fn round_trip_capture_update() {
+        round_trip(ServerMessageGeneric::CaptureUpdate(ServerCaptureUpdate {
+            player_amount: 12,
+            captures: captures(),
+        }));
+    }
+
+    #[test]
+    fn round_trip_player_change_name() {
+        round_trip(ServerMessageGeneric::PlayerChangeName(
+            ServerPlayerChangeName {
+                player_id: player_id(2),
+                name: "Ferris".parse().unwrap(),
+            },
+        ));
+    }
+
+    #[test]
+    fn round_trip_arena_wait_for_players() {
+        round_trip(ServerMessageGeneric::ArenaWaitForPlayers(
+            ServerArenaWaitForPlayers,
+        ));
+    }
+
+    #[test]
+    fn round_trip_arena_endround() {
+        round_trip(ServerMessageGeneric::ArenaEndround(ServerArenaEndround {
+            winners: Team::Blu,
+        }));
+    }
+
+    #[test]
+    fn round_trip_arena_restart() {
+        round_trip(ServerMessageGeneric::ArenaRestart(ServerArenaRestart));
+    }
+
+    #[test]
+    fn round_trip_unlock_cp() {
+        round_trip(ServerMessageGeneric::UnlockCp(ServerUnlockCp));
+    }
+
+    #[test]
+    fn round_trip_arena_startround() {
+        round_trip(ServerMessageGeneric::ArenaStartround(ServerArenaStartround));
+    }
+
+    #[test]
+    fn round_trip_toggle_zoom() {
+        round_trip(ServerMessageGeneric::ToggleZoom(ServerToggleZoom {
+            player_id: player_id(1),
+        }));
+    }
+
+    #[test]
+    fn round_trip_server_kick() {
+        round_trip(ServerMessageGeneric::ServerKick(ServerServerKick));
+    }
+
+    #[test]
+    fn round_trip_kick() {
+        for reason in [
+            KickReason::Name,
+            KickReason::BadPluginPacket,
+            KickReason::MultiClient,
+        ] {
+            round_trip(ServerMessageGeneric::Kick(ServerKick { reason }));
+        }
+    }
+
+    #[test]
+    fn round_trip_kick_name() {
+        round_trip(ServerMessageGeneric::KickName(ServerKickName));
+    }
+
+    #[test]
+    fn round_trip_return_intel() {
+        round_trip(ServerMessageGeneric::ReturnIntel(ServerReturnIntel {
+            team: TeamSpawnable::Blu,
+        }));
+    }
+
+    #[test]
+    fn round_trip_incompatible_protocol() {
+        round_trip(ServerMessageGeneric::IncompatibleProtocol(
+            ServerIncompatibleProtocol,
+        ));
+    }
+
+    #[test]
+    fn round_trip_join_update() {
+        round_trip(ServerMessageGeneric::JoinUpdate(ServerJoinUpdate {
+            client_player_id: player_id(7),
+            map_area: 1,
+        }));
+    }
+
+    #[test]
+    fn round_trip_download_map() {
+        round_trip(ServerMessageGeneric::DownloadMap(ServerDownloadMap {
+            length: 70_000,
+        }));
+    }
+
+    #[test]
+    fn round_trip_sentry_position() {
+        round_trip(ServerMessageGeneric::SentryPosition(ServerSentryPosition {
+            player_id: player_id(3),
+            position: Vec2::new(512.0, 128.0),
+        }));
+    }
+
+    #[test]
+    fn round_trip_reward_update() {
+        round_trip(ServerMessageGeneric::RewardUpdate(ServerRewardUpdate {
+            player_id: player_id(2),
+            rewards: "TopHat:Badge".to_string().try_into().unwrap(),
+        }));
+    }
+
+    #[test]
+    fn round_trip_reward_challenge_code() {
+        round_trip(ServerMessageGeneric::RewardChallengeCode(
+            ServerRewardChallengeCode {
+                challenge: std::array::from_fn(|index| index as u8 * 7),
+            },
+        ));
+    }
+
+    #[test]
+    fn round_trip_message_string() {
+        round_trip(ServerMessageGeneric::MessageString(ServerMessageString {
+            message: "Welcome!".parse().unwrap(),
+        }));
+    }
+
+    #[test]
+    fn round_trip_weapon_fire() {
+        round_trip(ServerMessageGeneric::WeaponFire(ServerWeaponFire {
+            player_id: player_id(5),
+            position: Vec2::new(320.0, 64.2),
+            velocity: Vec2::new(4.0, 0.0),
+            seed: 4242,
+        }));
+    }
+
+    #[test]
+    fn round_trip_plugin_packet() {
+        round_trip(ServerMessageGeneric::PluginPacket(ServerPluginPacket {
+            plugin_id: 3,
+            payload: b"Hello, plugin!".to_vec(),
+        }));
+    }
+
+    #[test]
+    fn round_trip_kick_bad_plugin_packet() {
+        round_trip(ServerMessageGeneric::KickBadPluginPacket(
+            ServerKickBadPluginPacket,
+        ));
+    }
+
+    #[test]
+    fn round_trip_ping() {
+        round_trip(ServerMessageGeneric::Ping(ServerPing));
+    }
+
+    #[test]
+    fn round_trip_kick_multi_client() {
+        round_trip(ServerMessageGeneric::KickMultiClient(ServerKickMultiClient));
+    }
+
+    #[test]
+    fn round_trip_reserve_slot() {
+        round_trip(ServerMessageGeneric::ReserveSlot(ServerReserveSlot));
+    }
+
+    fn round_trip_hud<T>(hud: T)
+    where
+        T: ServerNetworkSerializeHud + ClientNetworkDeserializeHud + PartialEq + std::fmt::Debug,
+    {
+        let mut buffer = Vec::new();
+        hud.serialize(&mut buffer).unwrap();
+
+        let mut payload = MessageReader::new(&buffer);
+        assert_eq!(T::deserialize(&mut payload).unwrap(), hud);
+        assert!(payload.is_empty(), "Payload wasn't fully read");
+    }
+
+    #[test]
+    fn round_trip_hud_arena() {
+        round_trip_hud(GamemodeHudArenaFull {
+            red_wins: 2,
+            blu_wins: 1,
+            state: 1,
+            winners: 0,
+            end_count: 300,
+        });
+    }
+
+    #[test]
+    fn round_trip_hud_control_point() {
+        round_trip_hud(RawControlPoint {
+            team: None,
+            capturing_team: Some(TeamSpawnable::Red),
+            capturing: 120,
+        });
+        round_trip_hud(RawControlPoint {
+            team: Some(TeamSpawnable::Blu),
+            capturing_team: None,
+            capturing: 0,
+        });
+    }
+
+    #[test]
+    fn round_trip_hud_koth_timer() {
+        round_trip_hud(HudKothTimer {
+            capture_unlock: Duration::from_secs(30),
+            red_timer: Duration::from_secs(180),
+            blu_timer: Duration::from_secs(97),
+        });
+    }
+
+    #[test]
+    fn round_trip_hud_generator() {
+        round_trip_hud(RawGenerator {
+            health: 4000,
+            shield_health: 250,
+        });
+    }
+
+    /// Values that survive being written as a fixed point `u8`
+    fn fixed_point_u8(scale: f32) -> impl Strategy<Value = f32> {
+        any::<u8>().prop_map(move |value| f32::from(value) / scale)
+    }
+
+    /// Values that survive being written as a fixed point `u16`
+    fn fixed_point_u16(scale: f32) -> impl Strategy<Value = f32> {
+        any::<u16>().prop_map(move |value| f32::from(value) / scale)
+    }
+
+    fn fixed_point_u8_vec2(scale: f32) -> impl Strategy<Value = Vec2> {
+        (fixed_point_u8(scale), fixed_point_u8(scale)).prop_map(|(x, y)| Vec2::new(x, y))
+    }
+
+    fn fixed_point_u16_vec2(scale: f32) -> impl Strategy<Value = Vec2> {
+        (fixed_point_u16(scale), fixed_point_u16(scale)).prop_map(|(x, y)| Vec2::new(x, y))
+    }
+
+    fn any_player_id() -> impl Strategy<Value = PlayerId> {
+        (0..u8::MAX).prop_map(player_id)
+    }
+
+    fn any_short_string() -> impl Strategy<Value = GGStringShort> {
+        "\\PC{0,80}".prop_filter_map("Longer than 255 bytes", |string| string.parse().ok())
+    }
+
+    fn any_map_name() -> impl Strategy<Value = GGStringShort> {
+        "[a-z0-9_-]{1,32}".prop_map(|string| string.parse().unwrap())
+    }
+
+    fn any_damage_source() -> impl Strategy<Value = DamageSource> {
+        select(
+            (0..=u8::MAX)
+                .filter_map(|value| DamageSource::try_from(value).ok())
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn any_raw_input() -> impl Strategy<Value = RawInput> {
+        (any::<u8>(), any::<u16>(), fixed_point_u8(2.0)).prop_map(
+            |(key_state, aim_direction, aim_distance)| RawInput {
+                key_state: key_state.into(),
+                aim_direction,
+                aim_distance,
+            },
+        )
+    }
+
+    fn any_raw_player_info() -> impl Strategy<Value = RawPlayerInfo> {
+        (
+            fixed_point_u16_vec2(5.0),
+            fixed_point_u8_vec2(8.5),
+            any::<u8>(),
+            any::<u8>(),
+            any::<u8>(),
+        )
+            .prop_map(|(translation, velocity, health, ammo_count, move_status)| {
+                RawPlayerInfo {
+                    translation,
+                    velocity,
+                    health,
+                    ammo_count,
+                    move_status,
+                }
+            })
+    }
+
+    fn any_moving_platform() -> impl Strategy<Value = RawMovingPlatform> {
+        (fixed_point_u16_vec2(5.0), any::<bool>()).prop_map(|(translation, returning)| {
+            RawMovingPlatform {
+                translation,
+                returning,
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_any_change_map(map_name in any_map_name(), map_md5 in option::of(any::<u128>())) {
+            round_trip(ServerMessageGeneric::ChangeMap(ServerChangeMap { map_name, map_md5 }));
+        }
+
+        #[test]
+        fn round_trip_any_player_spawn(player_id in any_player_id(), spawn_index in any::<u8>(), spawn_group in any::<u8>()) {
+            round_trip(ServerMessageGeneric::PlayerSpawn(ServerPlayerSpawn {
+                player_id,
+                spawn_index,
+                spawn_group,
+            }));
+        }
+
+        #[test]
+        fn round_trip_any_player_death(
+            target in any_player_id(),
+            attacker in option::of(any_player_id()),
+            assist in option::of(any_player_id()),
+            damage_source in any_damage_source(),
+        ) {
+            round_trip(ServerMessageGeneric::PlayerDeath(ServerPlayerDeath {
+                target,
+                attacker,
+                assist,
+                damage_source,
+            }));
+        }
+
+        #[test]
+        fn round_trip_any_player_change_name(player_id in any_player_id(), name in any_short_string()) {
+            round_trip(ServerMessageGeneric::PlayerChangeName(ServerPlayerChangeName {
+                player_id,
+                name,
+            }));
+        }
+
+        #[test]
+        fn round_trip_any_input_state(inputs in vec(option::of(any_raw_input()), 0..=32)) {
+            round_trip(ServerMessageGeneric::InputState(ServerInputState { inputs }));
+        }
+
+        #[test]
+        fn round_trip_any_quick_update(
+            player_characters in vec(option::of((any_raw_input(), any_raw_player_info())), 0..=32),
+            moving_platforms in vec(any_moving_platform(), 0..=8),
+        ) {
+            let mut context = TestContext {
+                moving_platforms_length: moving_platforms.len() as u8,
+                ..Default::default()
+            };
+
+            round_trip_context(
+                ServerMessageGeneric::QuickUpdate(ServerQuickUpdate {
+                    player_characters,
+                    moving_platforms,
+                }),
+                &mut context,
+            );
+        }
+
+        #[test]
+        fn round_trip_any_sentry_position(player_id in any_player_id(), position in fixed_point_u16_vec2(5.0)) {
+            round_trip(ServerMessageGeneric::SentryPosition(ServerSentryPosition {
+                player_id,
+                position,
+            }));
+        }
+
+        #[test]
+        fn round_trip_any_weapon_fire(
+            player_id in any_player_id(),
+            position in fixed_point_u16_vec2(5.0),
+            velocity in fixed_point_u8_vec2(8.5),
+            seed in any::<u16>(),
+        ) {
+            round_trip(ServerMessageGeneric::WeaponFire(ServerWeaponFire {
+                player_id,
+                position,
+                velocity,
+                seed,
+            }));
+        }
+
+        #[test]
+        fn round_trip_any_message_string(message in any_short_string()) {
+            round_trip(ServerMessageGeneric::MessageString(ServerMessageString { message }));
+        }
+
+        #[test]
+        fn round_trip_any_plugin_packet(plugin_id in any::<u8>(), payload in vec(any::<u8>(), 0..1024)) {
+            round_trip(ServerMessageGeneric::PluginPacket(ServerPluginPacket { plugin_id, payload }));
+        }
+
+        #[test]
+        fn round_trip_any_reward_challenge_code(challenge in any::<[u8; 16]>()) {
+            round_trip(ServerMessageGeneric::RewardChallengeCode(ServerRewardChallengeCode {
+                challenge,
+            }));
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use gg2_common::networking::PacketKind;
+
+/// The stages a client goes through when joining a server
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ClientConnectionState {
+    /// Connected but hasn't sent a hello
+    #[default]
+    AwaitingHello,
+    /// Was sent a password request and must reply with a bare password
+    AwaitingPassword,
+    /// Can reserve a player slot
+    AwaitingReserveSlot,
+    /// Has a reserved slot and can join
+    AwaitingPlayerJoin,
+    /// Is a player on the server
+    InGame,
+}
+
+impl ClientConnectionState {
+    /// Whether a message of this kind is allowed from the client at this point
+    pub fn allows(self, kind: PacketKind) -> bool {
+        matches!(
+            (self, kind),
+            (Self::AwaitingHello, PacketKind::Hello)
+                | (
+                    Self::AwaitingReserveSlot,
+                    PacketKind::ReserveSlot | PacketKind::DownloadMap
+                )
+                | (Self::AwaitingPlayerJoin, PacketKind::PlayerJoin)
+                | (
+                    Self::InGame,
+                    PacketKind::ClientSettings
+                        | PacketKind::InputState
+                        | PacketKind::Ping
+                        | PacketKind::PlayerChangeClass
+                        | PacketKind::PlayerChangeTeam
+                        | PacketKind::PluginPacket
+                        | PacketKind::RewardRequest
+                        | PacketKind::RewardChallengeResponse
+                )
+        )
+    }
+}